@@ -0,0 +1,273 @@
+use crate::hash::tree_hash;
+use crate::install::{confirm, glob_match};
+use crate::managed::{self, Ownership};
+use crate::skills::{copy_skill, discover_skills, record_sync, symlink_skill, SyncMethod};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One copy of a skill found in a target: (target label, target dir, tree hash).
+type FoundCopy = (String, PathBuf, String);
+
+/// What adopting a skill found in the targets would do.
+#[derive(Debug, PartialEq)]
+enum Plan {
+    /// Copy it into the source (all copies are identical)
+    Adopt,
+    /// The source already has an identical copy; only take over the originals
+    InSource,
+    /// The copies differ from each other
+    Conflict,
+    /// The source has a different skill with this name
+    DiffersFromSource,
+}
+
+/// Skills in `targets` that skillset doesn't manage yet, by name, limited to `patterns`
+/// (every skill when empty).
+fn find_unmanaged(
+    source_dir: &Path,
+    targets: &[(String, PathBuf)],
+    patterns: &[String],
+) -> Result<BTreeMap<String, Vec<FoundCopy>>> {
+    let mut found: BTreeMap<String, Vec<FoundCopy>> = BTreeMap::new();
+    for (label, target_dir) in targets {
+        for name in discover_skills(target_dir)? {
+            if !patterns.is_empty() && !patterns.iter().any(|p| glob_match(p, &name)) {
+                continue;
+            }
+            if managed::ownership(target_dir, &name, source_dir) != Ownership::Foreign {
+                continue;
+            }
+            let hash = tree_hash(&target_dir.join(&name))?;
+            found
+                .entry(name)
+                .or_default()
+                .push((label.clone(), target_dir.clone(), hash));
+        }
+    }
+    Ok(found)
+}
+
+fn plan(source_dir: &Path, name: &str, copies: &[FoundCopy]) -> Plan {
+    let first = &copies[0].2;
+    if copies.iter().any(|(_, _, hash)| hash != first) {
+        return Plan::Conflict;
+    }
+    let source = source_dir.join(name);
+    if !source.exists() {
+        return Plan::Adopt;
+    }
+    match tree_hash(&source) {
+        Ok(hash) if hash == *first => Plan::InSource,
+        _ => Plan::DiffersFromSource,
+    }
+}
+
+fn labels(copies: &[&FoundCopy]) -> String {
+    copies
+        .iter()
+        .map(|(label, _, _)| label.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Import skills found in the targets into the source. Identical copies across tools are
+/// adopted once; differing copies are reported and skipped. The originals become managed
+/// copies, or symlinks to the new source skill with `link`.
+pub fn adopt(
+    source_dir: &Path,
+    targets: &[(String, PathBuf)],
+    patterns: &[String],
+    link: bool,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let found = find_unmanaged(source_dir, targets, patterns)?;
+    if found.is_empty() {
+        println!("No unmanaged skills found in the targets.");
+        return Ok(());
+    }
+
+    let prefix = if dry_run { "[DRY RUN] " } else { "" };
+    println!("{}Adopting into {}:", prefix, source_dir.display());
+    let mut adopted = Vec::new();
+    let mut skipped = 0;
+    for (name, copies) in &found {
+        let all: Vec<&FoundCopy> = copies.iter().collect();
+        let identical = if copies.len() > 1 {
+            " (identical copies)"
+        } else {
+            ""
+        };
+        match plan(source_dir, name, copies) {
+            Plan::Adopt => {
+                println!("  + {}  from {}{}", name, labels(&all), identical);
+                adopted.push(name);
+            }
+            Plan::InSource => {
+                println!(
+                    "  = {}  already in the source, same as {}",
+                    name,
+                    labels(&all)
+                );
+                adopted.push(name);
+            }
+            Plan::Conflict => {
+                let mut variants: BTreeMap<&str, Vec<&FoundCopy>> = BTreeMap::new();
+                for copy in copies {
+                    variants.entry(copy.2.as_str()).or_default().push(copy);
+                }
+                let variants: Vec<String> = variants.values().map(|v| labels(v)).collect();
+                println!(
+                    "  ! {}  differs between tools ({}); skipped",
+                    name,
+                    variants.join(" | ")
+                );
+                println!(
+                    "      Hint: Adopt one copy with `skillset adopt {} --from <tool>`.",
+                    name
+                );
+                skipped += 1;
+            }
+            Plan::DiffersFromSource => {
+                println!(
+                    "  ! {}  in {} differs from the source copy; skipped",
+                    name,
+                    labels(&all)
+                );
+                println!(
+                    "      Hint: Replace the source copy with `skillset pull {} --from <tool>`, or remove the other copy.",
+                    name
+                );
+                skipped += 1;
+            }
+        }
+    }
+
+    let originals: Vec<(&String, &FoundCopy)> = adopted
+        .iter()
+        .flat_map(|name| found[*name].iter().map(move |copy| (*name, copy)))
+        .collect();
+    if dry_run {
+        if link && !originals.is_empty() {
+            println!(
+                "[DRY RUN] Would replace {} original(s) with symlinks to the source.",
+                originals.len()
+            );
+        }
+        println!("[DRY RUN] No changes made.");
+        return Ok(());
+    }
+
+    for name in &adopted {
+        let source = source_dir.join(name);
+        if !source.exists() {
+            let (_, target_dir, _) = &found[*name][0];
+            copy_skill(&target_dir.join(name), &source)?;
+        }
+    }
+
+    let link = link
+        && !originals.is_empty()
+        && (force
+            || confirm(&format!(
+                "Replace {} original(s) with symlinks to the source?",
+                originals.len()
+            ))?);
+    for (name, (label, target_dir, hash)) in &originals {
+        let skill_target = target_dir.join(name);
+        if link {
+            symlink_skill(&source_dir.join(name), &skill_target)?;
+            record_sync(
+                name,
+                &skill_target,
+                label,
+                source_dir,
+                SyncMethod::Symlink,
+                hash,
+            );
+            println!("  Linked {} at {}", name, label);
+        } else {
+            record_sync(
+                name,
+                &skill_target,
+                label,
+                source_dir,
+                SyncMethod::Copy,
+                hash,
+            );
+        }
+    }
+
+    println!(
+        "Adopted {} skill(s), skipped {} conflict(s).",
+        adopted.len(),
+        skipped
+    );
+    if !adopted.is_empty() {
+        println!("Run `skillset sync` to share them with the other targets.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_adopt_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn write_skill(dir: &Path, body: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), body).unwrap();
+    }
+
+    #[test]
+    fn plans_dedupe_identical_copies_and_flag_conflicts() {
+        let tmp = unique_tmp("plan");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join("source");
+        let cursor = tmp.join("cursor");
+        let claude = tmp.join("claude");
+        write_skill(&cursor.join("pdf"), "# PDF");
+        write_skill(&claude.join("pdf"), "# PDF");
+        write_skill(&cursor.join("xlsx"), "# XLSX v1");
+        write_skill(&claude.join("xlsx"), "# XLSX v2");
+        write_skill(&claude.join("notes"), "# Notes (tool)");
+        write_skill(&source.join("notes"), "# Notes");
+        write_skill(&source.join("docx"), "# DOCX");
+        write_skill(&cursor.join("docx"), "# DOCX");
+        let targets = vec![
+            ("Cursor".to_string(), cursor.clone()),
+            ("Claude".to_string(), claude.clone()),
+        ];
+
+        let found = find_unmanaged(&source, &targets, &[]).unwrap();
+        let plans: Vec<(String, usize, Plan)> = found
+            .iter()
+            .map(|(name, copies)| (name.clone(), copies.len(), plan(&source, name, copies)))
+            .collect();
+        let filtered: Vec<String> = find_unmanaged(&source, &targets, &["x*".to_string()])
+            .unwrap()
+            .into_keys()
+            .collect();
+
+        fs::remove_dir_all(&tmp).ok();
+        // docx has the same SKILL.md as the source, so it counts as managed and is left out.
+        assert_eq!(
+            plans,
+            vec![
+                ("notes".to_string(), 1, Plan::DiffersFromSource),
+                ("pdf".to_string(), 2, Plan::Adopt),
+                ("xlsx".to_string(), 2, Plan::Conflict),
+            ]
+        );
+        assert_eq!(filtered, vec!["xlsx".to_string()]);
+    }
+}
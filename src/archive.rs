@@ -0,0 +1,382 @@
+use crate::hash::sha256_hex;
+use crate::install::Fetch;
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+const ARCHIVE_EXTENSIONS: [&str; 4] = [".tar.gz", ".tgz", ".tar", ".zip"];
+
+/// Check if a package spec (local path or URL) names a `.tar.gz`, `.tgz`, `.tar`, or `.zip` archive.
+pub fn is_archive(spec: &str) -> bool {
+    let name = spec.split(['?', '#']).next().unwrap_or(spec).to_lowercase();
+    ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Archive file name without its extension, used as the package directory name so a
+/// bundle containing a bare `SKILL.md` installs under the archive's name.
+fn archive_stem(spec: &str) -> String {
+    let name = spec.split(['?', '#']).next().unwrap_or(spec);
+    let file = name.rsplit('/').next().unwrap_or(name);
+    let lower = file.to_lowercase();
+    let ext_len = ARCHIVE_EXTENSIONS
+        .iter()
+        .find(|ext| lower.ends_with(*ext))
+        .map_or(0, |ext| ext.len());
+    let stem = &file[..file.len() - ext_len];
+    if stem.is_empty() {
+        "package".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+fn archives_cache_dir(cache_root: &Path) -> Result<PathBuf> {
+    let dir = cache_root.join("archives");
+    fs::create_dir_all(&dir).context("Failed to create archive cache directory")?;
+    Ok(dir)
+}
+
+/// Unpack a local archive into the cache, keyed by its contents so an edited archive
+/// is extracted again. Returns the package directory.
+pub fn unpack_local(archive: &Path) -> Result<PathBuf> {
+    let bytes =
+        fs::read(archive).with_context(|| format!("Failed to read {}", archive.display()))?;
+    let key = format!("archive-{}", &sha256_hex(&bytes)[..16]);
+    let dir = archives_cache_dir(&crate::cache::cache_root()?)?.join(key);
+    let spec = archive.to_string_lossy();
+
+    if dir.exists() {
+        println!("Archive already unpacked at: {}", dir.display());
+    } else {
+        println!("Unpacking {}...", archive.display());
+        extract_into(archive, &spec, &dir)?;
+    }
+    package_dir(&dir, &spec)
+}
+
+/// Download an http(s) archive into the cache and unpack it. A cached download is reused
+/// unless `fetch` is `Refresh`; `Offline` never downloads. Returns the package directory.
+pub fn unpack_remote(url: &str, fetch: Fetch) -> Result<PathBuf> {
+    unpack_remote_in(&crate::cache::cache_root()?, url, fetch)
+}
+
+/// `unpack_remote` against the cache rooted at `cache_root`.
+fn unpack_remote_in(cache_root: &Path, url: &str, fetch: Fetch) -> Result<PathBuf> {
+    let cache = archives_cache_dir(cache_root)?;
+    let dir = cache.join(format!("url-{}", &sha256_hex(url.as_bytes())[..16]));
+
+    if dir.exists() && fetch != Fetch::Refresh {
+        println!("Archive already unpacked at: {}", dir.display());
+        return package_dir(&dir, url);
+    }
+    if fetch == Fetch::Offline {
+        anyhow::bail!(
+            "{} is not in the archive cache and offline mode is on\nHint: Run the command once without --offline (or SKILLSET_OFFLINE) to download it.",
+            url
+        );
+    }
+
+    println!("Downloading {}...", url);
+    let download = dir.with_extension("download");
+    let status = Command::new("curl")
+        .args([
+            "--fail",
+            "--silent",
+            "--show-error",
+            "--location",
+            "--output",
+        ])
+        .arg(&download)
+        .arg(url)
+        .status()
+        .context("Failed to run curl\nHint: Install curl to download archive packages.")?;
+    if !status.success() {
+        let _ = fs::remove_file(&download);
+        anyhow::bail!("Failed to download archive: {}", url);
+    }
+
+    let result = extract_into(&download, url, &dir);
+    let _ = fs::remove_file(&download);
+    result?;
+    package_dir(&dir, url)
+}
+
+/// Extract `archive` into `dir/<stem>`, replacing any previous extraction. Entries are
+/// unpacked into a staging directory first so a rejected archive leaves no partial package.
+fn extract_into(archive: &Path, spec: &str, dir: &Path) -> Result<()> {
+    let staging = dir.with_extension("partial");
+    let _ = fs::remove_dir_all(&staging);
+    let target = staging.join(archive_stem(spec));
+    fs::create_dir_all(&target).context("Failed to create archive staging directory")?;
+
+    let name = spec.split(['?', '#']).next().unwrap_or(spec).to_lowercase();
+    let result = if name.ends_with(".zip") {
+        extract_zip(archive, &target)
+    } else {
+        let file =
+            File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+        if name.ends_with(".tar") {
+            extract_tar(file, &target)
+        } else {
+            extract_tar(flate2::read::GzDecoder::new(file), &target)
+        }
+    };
+    if let Err(err) = result {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err.context(format!("Failed to unpack archive: {}", spec)));
+    }
+
+    let _ = fs::remove_dir_all(dir);
+    fs::rename(&staging, dir).context("Failed to move unpacked archive into the cache")?;
+    Ok(())
+}
+
+/// The package directory inside an extraction: archives that wrap everything in a
+/// single top-level folder (e.g. `bundle-1.0/`) resolve to that folder.
+fn package_dir(dir: &Path, spec: &str) -> Result<PathBuf> {
+    let root = dir.join(archive_stem(spec));
+    if root.join("SKILL.md").is_file() {
+        return Ok(root);
+    }
+
+    let entries: Vec<PathBuf> = fs::read_dir(&root)
+        .with_context(|| format!("Failed to read {}", root.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    match entries.as_slice() {
+        [only] if only.is_dir() && !only.join("SKILL.md").is_file() => Ok(only.clone()),
+        _ => Ok(root),
+    }
+}
+
+/// Validate an archive entry path, rejecting absolute paths and `..` components.
+fn safe_entry_path(path: &Path) -> Result<PathBuf> {
+    let mut safe = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => safe.push(part),
+            Component::CurDir => {}
+            _ => anyhow::bail!(
+                "Archive entry '{}' escapes the package directory",
+                path.display()
+            ),
+        }
+    }
+    Ok(safe)
+}
+
+fn extract_tar<R: Read>(reader: R, target: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().context("Failed to read tar archive")? {
+        let mut entry = entry.context("Failed to read tar entry")?;
+        let path = entry.path().context("Invalid tar entry path")?.into_owned();
+        let relative = safe_entry_path(&path)?;
+        let kind = entry.header().entry_type();
+
+        if kind.is_dir() {
+            fs::create_dir_all(target.join(&relative))?;
+        } else if kind.is_file() {
+            let dest = target.join(&relative);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            entry
+                .unpack(&dest)
+                .with_context(|| format!("Failed to extract {}", path.display()))?;
+        } else if kind.is_symlink() || kind.is_hard_link() {
+            anyhow::bail!(
+                "Archive entry '{}' is a link; links are not supported in skill archives",
+                path.display()
+            );
+        }
+        // Other entry types (pax global headers, etc.) carry no files.
+    }
+    Ok(())
+}
+
+fn extract_zip(archive: &Path, target: &Path) -> Result<()> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut zip = zip::ZipArchive::new(file).context("Failed to read zip archive")?;
+
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).context("Failed to read zip entry")?;
+        let relative = safe_entry_path(Path::new(entry.name()))?;
+        let dest = target.join(&relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&dest)?;
+            continue;
+        }
+        if entry.is_symlink() {
+            anyhow::bail!(
+                "Archive entry '{}' is a link; links are not supported in skill archives",
+                entry.name()
+            );
+        }
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut out =
+            File::create(&dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+        std::io::copy(&mut entry, &mut out)
+            .with_context(|| format!("Failed to extract {}", entry.name()))?;
+
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&dest, fs::Permissions::from_mode(mode & 0o777))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_archive_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn write_tar_gz(path: &Path, entries: &[(&str, &str)]) {
+        let file = File::create(path).unwrap();
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            // set_path rejects `..`, so write the raw name to build hostile archives.
+            let raw = &mut header.as_old_mut().name;
+            raw[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_cksum();
+            builder.append(&header, contents.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, contents) in entries {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn detects_archive_specs() {
+        assert!(is_archive("./bundle.tar.gz"));
+        assert!(is_archive("https://example.com/skills.ZIP?token=x"));
+        assert!(is_archive("file:///tmp/skills.tgz"));
+        assert!(!is_archive("org/repo"));
+        assert!(!is_archive("https://github.com/org/repo.git"));
+        assert_eq!(
+            archive_stem("https://example.com/my-skill.tar.gz"),
+            "my-skill"
+        );
+    }
+
+    #[test]
+    fn extracts_tar_gz_and_zip_bundles() {
+        let tmp = unique_tmp("extract");
+        let _ = fs::remove_dir_all(&tmp);
+        fs::create_dir_all(&tmp).unwrap();
+        let entries = [
+            ("bundle-1.0/skills/my-skill/SKILL.md", "# My Skill"),
+            ("bundle-1.0/skills/my-skill/scripts/run.sh", "echo hi"),
+        ];
+
+        let tgz = tmp.join("bundle.tar.gz");
+        write_tar_gz(&tgz, &entries);
+        extract_into(&tgz, "bundle.tar.gz", &tmp.join("out-tgz")).unwrap();
+        let tgz_dir = package_dir(&tmp.join("out-tgz"), "bundle.tar.gz").unwrap();
+
+        let zip = tmp.join("bundle.zip");
+        write_zip(&zip, &entries);
+        extract_into(&zip, "bundle.zip", &tmp.join("out-zip")).unwrap();
+        let zip_dir = package_dir(&tmp.join("out-zip"), "bundle.zip").unwrap();
+
+        let tgz_skill = tgz_dir.join("skills/my-skill/scripts/run.sh").is_file();
+        let zip_skill = zip_dir.join("skills/my-skill/SKILL.md").is_file();
+        fs::remove_dir_all(&tmp).ok();
+        assert!(tgz_dir.ends_with("bundle/bundle-1.0"));
+        assert!(tgz_skill);
+        assert!(zip_skill);
+    }
+
+    #[test]
+    fn rejects_path_traversal_entries() {
+        let tmp = unique_tmp("traversal");
+        let _ = fs::remove_dir_all(&tmp);
+        fs::create_dir_all(&tmp).unwrap();
+
+        let tgz = tmp.join("evil.tar.gz");
+        write_tar_gz(&tgz, &[("../escaped.md", "pwned")]);
+        let tar_err = extract_into(&tgz, "evil.tar.gz", &tmp.join("out-tgz")).unwrap_err();
+
+        let zip = tmp.join("evil.zip");
+        write_zip(&zip, &[("/abs/escaped.md", "pwned")]);
+        let zip_err = extract_into(&zip, "evil.zip", &tmp.join("out-zip")).unwrap_err();
+
+        let escaped = tmp.join("escaped.md").exists();
+        let leftovers = tmp.join("out-tgz").exists() || tmp.join("out-zip").exists();
+        fs::remove_dir_all(&tmp).ok();
+        assert!(format!("{:#}", tar_err).contains("escapes the package directory"));
+        assert!(format!("{:#}", zip_err).contains("escapes the package directory"));
+        assert!(!escaped);
+        assert!(!leftovers);
+    }
+
+    #[test]
+    fn downloads_archives_over_http() {
+        use std::net::TcpListener;
+
+        let tmp = unique_tmp("http");
+        let _ = fs::remove_dir_all(&tmp);
+        fs::create_dir_all(&tmp).unwrap();
+        let zip = tmp.join("served.zip");
+        write_zip(&zip, &[("served-skill/SKILL.md", "# Served")]);
+        let body = fs::read(&zip).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(header.as_bytes()).unwrap();
+            stream.write_all(&body).unwrap();
+        });
+
+        let url = format!(
+            "http://127.0.0.1:{}/served-{}.zip",
+            port,
+            std::process::id()
+        );
+        let cache = crate::cache::cache_root_in(&tmp.join("cache"));
+        let dir = unpack_remote_in(&cache, &url, Fetch::Refresh).unwrap();
+        let offline = unpack_remote_in(&cache, &format!("{}?missing", url), Fetch::Offline);
+        server.join().unwrap();
+
+        let installed = dir.join("served-skill").join("SKILL.md").is_file();
+        fs::remove_dir_all(&tmp).ok();
+        assert!(dir.starts_with(&cache));
+        assert!(installed);
+        assert!(offline.unwrap_err().to_string().contains("offline mode"));
+    }
+}
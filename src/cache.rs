@@ -0,0 +1,376 @@
+use crate::config::InstallConfig;
+use crate::install::{cache_names, git_output};
+use crate::{lockfile, manifest};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Metadata sidecar (`repos/<name>.json`) mapping a cache directory back to its package.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct CacheMeta {
+    /// Repository spec without ref or subdirectory (owner/repo or Git URL)
+    pub spec: String,
+    /// Remote URL the repo was cloned from
+    pub url: String,
+    /// Branch, tag, or commit the cache directory is pinned to
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Unix timestamp (seconds) of the last install, update, or outdated check
+    pub last_used: u64,
+    /// Skill source directories (e.g. `.skillset/skills`) that installed from this repo
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub used_by: BTreeSet<String>,
+}
+
+/// Environment variable that moves the skillset cache to another directory.
+pub const CACHE_DIR_ENV: &str = "SKILLSET_CACHE_DIR";
+
+/// Root of the skillset cache: `$SKILLSET_CACHE_DIR` when set, else `<cache dir>/skillset`.
+pub fn cache_root() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(CACHE_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    Ok(cache_root_in(
+        &dirs::cache_dir().context("Failed to determine cache directory")?,
+    ))
+}
+
+/// The skillset cache under `base`; tests pass a temp directory here.
+pub fn cache_root_in(base: &Path) -> PathBuf {
+    base.join("skillset")
+}
+
+/// Directory holding cloned package repos.
+pub fn repos_dir() -> Result<PathBuf> {
+    Ok(cache_root()?.join("repos"))
+}
+
+fn meta_path(repo_dir: &Path) -> PathBuf {
+    let name = repo_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    repo_dir.with_file_name(format!("{}.json", name))
+}
+
+pub fn load_meta(repo_dir: &Path) -> Option<CacheMeta> {
+    let content = fs::read_to_string(meta_path(repo_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_meta(repo_dir: &Path, meta: &CacheMeta) -> Result<()> {
+    let mut content =
+        serde_json::to_string_pretty(meta).context("Failed to serialize cache metadata")?;
+    content.push('\n');
+    fs::write(meta_path(repo_dir), content).context("Failed to write cache metadata")
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Record that a cached repo was just used, creating its sidecar if needed.
+pub fn touch(repo_dir: &Path, spec: &str, url: &str, git_ref: Option<&str>) -> Result<()> {
+    let mut meta = load_meta(repo_dir).unwrap_or_default();
+    meta.spec = spec.to_string();
+    meta.url = url.to_string();
+    meta.git_ref = git_ref.map(str::to_string);
+    meta.last_used = now_secs();
+    save_meta(repo_dir, &meta)
+}
+
+/// Remember that `source_dir` installed skills from a cached repo, so `cache prune`
+/// can check its manifest and lockfile. Does nothing for packages outside the cache.
+pub fn record_user(repo_dir: &Path, source_dir: &Path) -> Result<()> {
+    let Some(mut meta) = load_meta(repo_dir) else {
+        return Ok(());
+    };
+    let source = source_dir
+        .canonicalize()
+        .unwrap_or_else(|_| source_dir.to_path_buf());
+    if meta.used_by.insert(source.to_string_lossy().into_owned()) {
+        save_meta(repo_dir, &meta)?;
+    }
+    Ok(())
+}
+
+/// Cached repo directories (sidecar files are skipped).
+fn cached_repos(repos_dir: &Path) -> Result<Vec<PathBuf>> {
+    if !repos_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut repos: Vec<PathBuf> = fs::read_dir(repos_dir)
+        .with_context(|| format!("Failed to read {}", repos_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    repos.sort();
+    Ok(repos)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| dir_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn format_age(last_used: u64) -> String {
+    let elapsed = now_secs().saturating_sub(last_used);
+    match elapsed {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", elapsed / 60),
+        3600..=86399 => format!("{}h ago", elapsed / 3600),
+        _ => format!("{}d ago", elapsed / 86400),
+    }
+}
+
+/// Print each cached repo with its spec, URL, size, commit, and last use.
+pub fn list() -> Result<()> {
+    let repos_dir = repos_dir()?;
+    let repos = cached_repos(&repos_dir)?;
+    if repos.is_empty() {
+        println!("No cached repos in {}", repos_dir.display());
+        return Ok(());
+    }
+
+    println!("Cached repos in {}:\n", repos_dir.display());
+    let mut total = 0;
+    for repo in &repos {
+        let name = repo.file_name().unwrap_or_default().to_string_lossy();
+        let size = dir_size(repo);
+        total += size;
+        let commit = git_output(repo, &["rev-parse", "--short", "HEAD"]);
+        match load_meta(repo) {
+            Some(meta) => {
+                let spec = match &meta.git_ref {
+                    Some(r) => format!("{}@{}", meta.spec, r),
+                    None => meta.spec.clone(),
+                };
+                println!("  {}", spec);
+                println!("    url:       {}", meta.url);
+                println!("    dir:       {}", name);
+                println!("    commit:    {}", commit.as_deref().unwrap_or("-"));
+                println!("    size:      {}", format_size(size));
+                println!("    last used: {}", format_age(meta.last_used));
+            }
+            None => {
+                println!("  {} (no metadata)", name);
+                println!("    commit:    {}", commit.as_deref().unwrap_or("-"));
+                println!("    size:      {}", format_size(size));
+            }
+        }
+    }
+    println!("\n{} repo(s), {} total", repos.len(), format_size(total));
+    Ok(())
+}
+
+/// Cache directory names (including not-yet-migrated legacy names) referenced by the
+/// manifest or lockfile of any source dir. `config` resolves host aliases in package specs.
+fn referenced_names(source_dirs: &[PathBuf], config: &InstallConfig) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for source in source_dirs {
+        if let Ok(manifest) = manifest::load(source) {
+            for entry in manifest.packages {
+                names.extend(cache_names(
+                    &entry.package,
+                    entry.git_ref.as_deref(),
+                    config,
+                ));
+            }
+        }
+        if let Ok(lock) = lockfile::load(source) {
+            for entry in lock.skills.values() {
+                names.extend(cache_names(
+                    &entry.package,
+                    entry.git_ref.as_deref(),
+                    config,
+                ));
+            }
+        }
+    }
+    names
+}
+
+/// Remove cached repos that no known manifest or lockfile references. `source_dirs` are
+/// checked in addition to each repo's recorded users. Returns the removed directory names.
+fn prune_in(
+    repos_dir: &Path,
+    source_dirs: &[PathBuf],
+    config: &InstallConfig,
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    let mut removed = Vec::new();
+    for repo in cached_repos(repos_dir)? {
+        let name = repo
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        let mut sources = source_dirs.to_vec();
+        if let Some(meta) = load_meta(&repo) {
+            sources.extend(meta.used_by.iter().map(PathBuf::from));
+        }
+        if referenced_names(&sources, config).contains(&name) {
+            continue;
+        }
+
+        if dry_run {
+            println!("[DRY RUN] Would remove {}", name);
+        } else {
+            fs::remove_dir_all(&repo)
+                .with_context(|| format!("Failed to remove {}", repo.display()))?;
+            let _ = fs::remove_file(meta_path(&repo));
+            println!("  Removed {}", name);
+        }
+        removed.push(name);
+    }
+    Ok(removed)
+}
+
+/// Remove cached repos not referenced by any manifest or lockfile.
+pub fn prune(source_dirs: &[PathBuf], config: &InstallConfig, dry_run: bool) -> Result<()> {
+    let removed = prune_in(&repos_dir()?, source_dirs, config, dry_run)?;
+    if removed.is_empty() {
+        println!("No unused cached repos.");
+    } else if dry_run {
+        println!("[DRY RUN] No changes made.");
+    } else {
+        println!("Pruned {} cached repo(s).", removed.len());
+    }
+    Ok(())
+}
+
+/// Remove every cached repo and archive.
+pub fn clean(dry_run: bool) -> Result<()> {
+    let root = cache_root()?;
+    if !root.exists() {
+        println!("Cache is already empty ({}).", root.display());
+        return Ok(());
+    }
+    let size = format_size(dir_size(&root));
+    if dry_run {
+        println!("[DRY RUN] Would remove {} ({})", root.display(), size);
+        return Ok(());
+    }
+    fs::remove_dir_all(&root).with_context(|| format!("Failed to remove {}", root.display()))?;
+    println!("Removed {} ({})", root.display(), size);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedSkill;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_cache_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn sidecar_tracks_metadata_and_users() {
+        let tmp = unique_tmp("sidecar");
+        let _ = fs::remove_dir_all(&tmp);
+        let repo = tmp.join("repos").join("org-repo@v1.2.0");
+        fs::create_dir_all(&repo).unwrap();
+
+        touch(
+            &repo,
+            "org/repo",
+            "https://github.com/org/repo.git",
+            Some("v1.2.0"),
+        )
+        .unwrap();
+        record_user(&repo, &tmp.join("ws")).unwrap();
+        record_user(&repo, &tmp.join("ws")).unwrap();
+        let meta = load_meta(&repo).unwrap();
+        let sidecar = tmp.join("repos").join("org-repo@v1.2.0.json").is_file();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(sidecar);
+        assert_eq!(meta.spec, "org/repo");
+        assert_eq!(meta.git_ref.as_deref(), Some("v1.2.0"));
+        assert_eq!(meta.used_by.len(), 1);
+        assert!(meta.last_used > 0);
+    }
+
+    #[test]
+    fn prune_keeps_repos_referenced_by_recorded_users() {
+        let tmp = unique_tmp("prune");
+        let _ = fs::remove_dir_all(&tmp);
+        let repos = tmp.join("repos");
+        let source = tmp.join("ws").join(".skillset").join("skills");
+        let kept_name = cache_names("org/kept", None, &InstallConfig::default())[0].clone();
+        for name in [kept_name.as_str(), "org-unused", "url-0000000000000000"] {
+            fs::create_dir_all(repos.join(name)).unwrap();
+        }
+        for name in [kept_name.as_str(), "org-unused"] {
+            touch(&repos.join(name), name, "https://example.com", None).unwrap();
+            record_user(&repos.join(name), &source).unwrap();
+        }
+        lockfile::record(
+            &source,
+            vec![(
+                "my-skill".to_string(),
+                LockedSkill {
+                    package: "org/kept/skills".to_string(),
+                    git_ref: None,
+                    url: None,
+                    commit: None,
+                    subdir: None,
+                    skill: None,
+                    license: None,
+                    hash: "sha256-abc".to_string(),
+                    files: Default::default(),
+                },
+            )],
+        )
+        .unwrap();
+
+        let config = InstallConfig::default();
+        let dry = prune_in(&repos, &[], &config, true).unwrap();
+        let removed = prune_in(&repos, &[], &config, false).unwrap();
+        let kept = repos.join(&kept_name).exists();
+        let unused_meta = repos.join("org-unused.json").exists();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(dry, removed);
+        assert_eq!(removed, vec!["org-unused", "url-0000000000000000"]);
+        assert!(kept);
+        assert!(!unused_meta);
+    }
+}
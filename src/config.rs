@@ -1,5 +1,7 @@
+use crate::signature::check_keys;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -31,6 +33,160 @@ pub struct InstallConfig {
     /// Directories to look for skills in when installing from a repo (relative to repo root)
     #[serde(default = "default_skill_dirs")]
     pub skill_dirs: Vec<String>,
+    /// Host aliases for `alias:path/to/repo` specs (e.g. `"corp": "git.corp.example.com"`),
+    /// in addition to the built-in `github:`, `gitlab:`, and `bitbucket:`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hosts: BTreeMap<String, HostAlias>,
+    /// SPDX license IDs that may be installed (empty allows any license not denied)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_licenses: Vec<String>,
+    /// SPDX license IDs that may never be installed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_licenses: Vec<String>,
+    /// Whether a license policy violation blocks the install or only warns
+    #[serde(default, skip_serializing_if = "LicenseAction::is_default")]
+    pub on_license_violation: LicenseAction,
+    /// Trusted signing keys and whether unsigned packages are allowed
+    #[serde(default, skip_serializing_if = "SigningConfig::is_empty")]
+    pub signing: SigningConfig,
+    /// Install-time security scan of skill contents
+    #[serde(default, skip_serializing_if = "ScanConfig::is_default")]
+    pub scan: ScanConfig,
+    /// Clone package repos partially and sparsely, checking out only the installed skills.
+    /// Turn off for full clones that work offline for any skill in the package.
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub partial_clone: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LicenseAction {
+    #[default]
+    Block,
+    Warn,
+}
+
+impl LicenseAction {
+    fn is_default(&self) -> bool {
+        *self == LicenseAction::Block
+    }
+}
+
+/// Git host an `alias:` package spec points at: a hostname, or a hostname with its own
+/// SSH/HTTPS preference.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum HostAlias {
+    Host(String),
+    Detailed {
+        host: String,
+        /// Overrides `install.use_ssh` for this host
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        use_ssh: Option<bool>,
+    },
+}
+
+impl HostAlias {
+    pub fn host(&self) -> &str {
+        match self {
+            HostAlias::Host(host) | HostAlias::Detailed { host, .. } => host,
+        }
+    }
+
+    /// Whether to clone over SSH, falling back to the global `use_ssh` setting.
+    pub fn use_ssh(&self, default: bool) -> bool {
+        match self {
+            HostAlias::Detailed {
+                use_ssh: Some(use_ssh),
+                ..
+            } => *use_ssh,
+            _ => default,
+        }
+    }
+}
+
+impl InstallConfig {
+    /// Look up a host alias: configured aliases first, then the built-in ones.
+    pub fn host_alias(&self, name: &str) -> Option<HostAlias> {
+        if let Some(alias) = self.hosts.get(name) {
+            return Some(alias.clone());
+        }
+        let host = match name {
+            "github" => "github.com",
+            "gitlab" => "gitlab.com",
+            "bitbucket" => "bitbucket.org",
+            _ => return None,
+        };
+        Some(HostAlias::Host(host.to_string()))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SigningConfig {
+    /// Refuse to install packages without a signature from a trusted key
+    #[serde(default)]
+    pub require_signed: bool,
+    /// Package or host pattern -> trusted keys. Patterns are `owner/repo` (GitHub),
+    /// `host`, `host/owner/repo`, or globs such as `github.com/acme/*` and `*`.
+    /// Keys are SSH public keys (`ssh-ed25519 AAAA...`) or GPG fingerprints.
+    #[serde(default)]
+    pub trusted_keys: BTreeMap<String, Vec<String>>,
+}
+
+impl SigningConfig {
+    fn is_empty(&self) -> bool {
+        !self.require_signed && self.trusted_keys.is_empty()
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+fn default_max_binary_kb() -> u64 {
+    1024
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScanConfig {
+    /// Scan skills before copying them into the source
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Built-in rules to turn off: executable, pipe-to-shell, hidden-unicode,
+    /// prompt-injection, large-binary
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_rules: Vec<String>,
+    /// Extra phrases to flag in text files (case-insensitive)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    /// Files to skip, as globs on `skill/path` (e.g. `pdf/scripts/*`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// Binary files larger than this (in KiB) are flagged
+    #[serde(default = "default_max_binary_kb")]
+    pub max_binary_kb: u64,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            disabled_rules: Vec::new(),
+            patterns: Vec::new(),
+            ignore: Vec::new(),
+            max_binary_kb: default_max_binary_kb(),
+        }
+    }
+}
+
+impl ScanConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl Default for InstallConfig {
@@ -38,6 +194,13 @@ impl Default for InstallConfig {
         Self {
             use_ssh: true,
             skill_dirs: default_skill_dirs(),
+            hosts: BTreeMap::new(),
+            allowed_licenses: Vec::new(),
+            denied_licenses: Vec::new(),
+            on_license_violation: LicenseAction::default(),
+            signing: SigningConfig::default(),
+            scan: ScanConfig::default(),
+            partial_clone: true,
         }
     }
 }
@@ -122,6 +285,7 @@ pub fn load() -> Result<Config> {
     let content = fs::read_to_string(&path).context("Failed to read config file")?;
     let mut config: Config =
         serde_json::from_str(&content).context("Failed to parse config file")?;
+    check_keys(&config.install.signing)?;
 
     // Migrate legacy .ai/skills to .skillset/skills
     if config.source == ".ai/skills" {
@@ -388,4 +552,30 @@ mod tests {
         assert_eq!(hermes_targets.len(), 1);
         assert_eq!(hermes_targets[0].label, "Hermes");
     }
+
+    #[test]
+    fn test_host_aliases_accept_hostname_or_object() {
+        let install: InstallConfig = serde_json::from_str(
+            r#"{
+                "use_ssh": false,
+                "hosts": {
+                    "corp": "git.corp.example.com",
+                    "gitlab": { "host": "gitlab.internal", "use_ssh": true }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let corp = install.host_alias("corp").unwrap();
+        assert_eq!(corp.host(), "git.corp.example.com");
+        assert!(!corp.use_ssh(install.use_ssh));
+        let gitlab = install.host_alias("gitlab").unwrap();
+        assert_eq!(gitlab.host(), "gitlab.internal");
+        assert!(gitlab.use_ssh(install.use_ssh));
+        assert_eq!(
+            install.host_alias("bitbucket").unwrap().host(),
+            "bitbucket.org"
+        );
+        assert!(install.host_alias("unknown").is_none());
+    }
 }
@@ -0,0 +1,190 @@
+use crate::lockfile;
+use crate::skills::discover_skills;
+use crate::validate::parse_frontmatter;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// One `requires:` entry from SKILL.md frontmatter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    pub skill: String,
+    /// Package that provides the skill (`package::skill`); None means the same package
+    /// or a skill already in the source
+    pub package: Option<String>,
+}
+
+impl Requirement {
+    pub fn parse(entry: &str) -> Self {
+        match entry.rsplit_once("::") {
+            Some((package, skill)) if !package.is_empty() => Self {
+                skill: skill.trim().to_string(),
+                package: Some(package.trim().to_string()),
+            },
+            _ => Self {
+                skill: entry.trim().trim_start_matches("::").to_string(),
+                package: None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.package {
+            Some(package) => write!(f, "{}::{}", package, self.skill),
+            None => write!(f, "{}", self.skill),
+        }
+    }
+}
+
+/// Requirements declared in a skill directory's SKILL.md (empty if it has none).
+pub fn skill_requirements(skill_dir: &Path) -> Vec<Requirement> {
+    fs::read_to_string(skill_dir.join("SKILL.md"))
+        .ok()
+        .and_then(|content| parse_frontmatter(&content))
+        .map(|meta| {
+            meta.requires
+                .iter()
+                .map(|r| Requirement::parse(r))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Names that satisfy a requirement in `source`: installed skill names plus the original
+/// names of skills installed under `--as` or `--prefix`.
+pub fn provided_names(source: &Path) -> HashSet<String> {
+    let mut names: HashSet<String> = discover_skills(source)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    if let Ok(lock) = lockfile::load(source) {
+        names.extend(
+            lock.skills
+                .iter()
+                .filter(|(name, _)| source.join(name).is_dir())
+                .filter_map(|(_, locked)| locked.skill.clone()),
+        );
+    }
+    names
+}
+
+/// Skills in `source` with requirements that are not installed there, as
+/// (skill, requirement) pairs.
+pub fn missing_requirements(source: &Path) -> Result<Vec<(String, Requirement)>> {
+    let provided = provided_names(source);
+    let mut missing = Vec::new();
+    for skill in discover_skills(source)? {
+        for requirement in skill_requirements(&source.join(&skill)) {
+            if !provided.contains(&requirement.skill) {
+                missing.push((skill.clone(), requirement));
+            }
+        }
+    }
+    Ok(missing)
+}
+
+/// Print a warning for each skill in `source` whose requirements are not installed.
+pub fn warn_missing(source: &Path) -> Result<()> {
+    for (skill, requirement) in missing_requirements(source)? {
+        eprintln!(
+            "Warning: '{}' requires '{}', which is not in the source.",
+            skill, requirement.skill
+        );
+        match &requirement.package {
+            Some(package) => eprintln!(
+                "  Hint: Run `skillset install {} --skill={}`.",
+                package, requirement.skill
+            ),
+            None => eprintln!(
+                "  Hint: Install or add a skill named '{}'.",
+                requirement.skill
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Other skills in `source` that require `name` (by installed or original name).
+pub fn dependents(source: &Path, name: &str) -> Result<Vec<String>> {
+    let original = lockfile::load(source)
+        .ok()
+        .and_then(|lock| lock.skills.get(name).and_then(|l| l.skill.clone()));
+    let provides = |skill: &str| skill == name || original.as_deref() == Some(skill);
+    Ok(discover_skills(source)?
+        .into_iter()
+        .filter(|skill| skill != name)
+        .filter(|skill| {
+            skill_requirements(&source.join(skill))
+                .iter()
+                .any(|requirement| provides(&requirement.skill))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_deps_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn write_skill(source: &Path, name: &str, requires: &str) {
+        let dir = source.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: {}\ndescription: d\nrequires: [{}]\n---\n",
+                name, requires
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn requirement_parses_optional_package() {
+        assert_eq!(
+            Requirement::parse("k8s-conventions"),
+            Requirement {
+                skill: "k8s-conventions".to_string(),
+                package: None
+            }
+        );
+        let requirement = Requirement::parse("acme/ops-skills@v2::helm");
+        assert_eq!(requirement.package.as_deref(), Some("acme/ops-skills@v2"));
+        assert_eq!(requirement.skill, "helm");
+        assert_eq!(requirement.to_string(), "acme/ops-skills@v2::helm");
+    }
+
+    #[test]
+    fn finds_missing_requirements_and_dependents() {
+        let tmp = unique_tmp("source");
+        let _ = fs::remove_dir_all(&tmp);
+        write_skill(&tmp, "deploy", "k8s-conventions, acme/ops::helm");
+        write_skill(&tmp, "k8s-conventions", "");
+        write_skill(&tmp, "review", "deploy");
+
+        let missing = missing_requirements(&tmp).unwrap();
+        let deploy_dependents = dependents(&tmp, "deploy").unwrap();
+        let k8s_dependents = dependents(&tmp, "k8s-conventions").unwrap();
+        let review_dependents = dependents(&tmp, "review").unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].0, "deploy");
+        assert_eq!(missing[0].1.to_string(), "acme/ops::helm");
+        assert_eq!(deploy_dependents, vec!["review"]);
+        assert_eq!(k8s_dependents, vec!["deploy"]);
+        assert!(review_dependents.is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Hex-encoded SHA-256 of a byte slice.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hash a skill directory tree (relative paths, executable bits and file contents).
+/// Symlinked directories contribute their link target instead of their contents.
+/// Returns a `sha256-<hex>` string that is stable across platforms and runs.
+pub fn tree_hash(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    let mut links = Vec::new();
+    walk(dir, dir, &mut files, &mut links)?;
+    files.sort();
+    links.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in &files {
+        let path = dir.join(relative);
+        let contents =
+            fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let mode = if is_executable(&path) { "x" } else { "-" };
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(mode.as_bytes());
+        hasher.update([0]);
+        hasher.update(sha256_hex(&contents).as_bytes());
+        hasher.update([b'\n']);
+    }
+    for (relative, target) in &links {
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(b"l");
+        hasher.update([0]);
+        hasher.update(sha256_hex(target.as_bytes()).as_bytes());
+        hasher.update([b'\n']);
+    }
+
+    Ok(format!("sha256-{}", to_hex(&hasher.finalize())))
+}
+
+/// Hash every file in a skill directory: relative `/` path -> `sha256-<hex>` of its contents.
+/// Executable files get an `x:` prefix so mode changes show up per file, and symlinked
+/// directories an `l:` prefix over their link target.
+pub fn file_hashes(dir: &Path) -> Result<BTreeMap<String, String>> {
+    let mut files = Vec::new();
+    let mut links = Vec::new();
+    walk(dir, dir, &mut files, &mut links)?;
+
+    let mut hashes = files
+        .into_iter()
+        .map(|relative| {
+            let path = dir.join(&relative);
+            let contents =
+                fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+            let mode = if is_executable(&path) { "x:" } else { "" };
+            Ok((
+                relative,
+                format!("{}sha256-{}", mode, sha256_hex(&contents)),
+            ))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    for (relative, target) in links {
+        hashes.insert(
+            relative,
+            format!("l:sha256-{}", sha256_hex(target.as_bytes())),
+        );
+    }
+    Ok(hashes)
+}
+
+/// Collect file paths under `dir` relative to `root`, using `/` as separator.
+/// Symlinked directories are not descended into, so a link to `..` can't loop.
+pub fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<()> {
+    walk(root, dir, files, &mut Vec::new())
+}
+
+/// Like `collect_files`, also listing symlinked directories as (relative path, link target).
+fn walk(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<String>,
+    links: &mut Vec<(String, String)>,
+) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+
+    for entry in entries {
+        let entry = entry.context("Failed to read directory entry")?;
+        let path = entry.path();
+        let metadata = fs::symlink_metadata(&path)
+            .with_context(|| format!("Failed to inspect {}", path.display()))?;
+        if metadata.file_type().is_symlink() && path.is_dir() {
+            let target = fs::read_link(&path)
+                .with_context(|| format!("Failed to read link {}", path.display()))?;
+            links.push((
+                relative_path(root, &path)?,
+                target.to_string_lossy().into_owned(),
+            ));
+        } else if metadata.is_dir() {
+            walk(root, &path, files, links)?;
+        } else if path.is_file() {
+            files.push(relative_path(root, &path)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn relative_path(root: &Path, path: &Path) -> Result<String> {
+    let relative = path
+        .strip_prefix(root)
+        .context("File is outside the hashed directory")?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Ok(parts.join("/"))
+}
+
+#[cfg(unix)]
+pub fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_hash_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn tree_hash_is_stable_for_identical_trees() {
+        let tmp = unique_tmp("identical");
+        let _ = fs::remove_dir_all(&tmp);
+        for side in ["a", "b"] {
+            let skill = tmp.join(side).join("my-skill");
+            fs::create_dir_all(skill.join("scripts")).unwrap();
+            fs::write(skill.join("SKILL.md"), "# My Skill").unwrap();
+            fs::write(skill.join("scripts").join("run.sh"), "echo hi").unwrap();
+        }
+
+        let a = tree_hash(&tmp.join("a").join("my-skill")).unwrap();
+        let b = tree_hash(&tmp.join("b").join("my-skill")).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(a, b);
+        assert!(a.starts_with("sha256-"));
+    }
+
+    #[test]
+    fn tree_hash_changes_when_nested_file_changes() {
+        let tmp = unique_tmp("nested");
+        let _ = fs::remove_dir_all(&tmp);
+        let skill = tmp.join("my-skill");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(skill.join("SKILL.md"), "# My Skill").unwrap();
+        fs::write(skill.join("scripts").join("run.sh"), "echo hi").unwrap();
+        let before = tree_hash(&skill).unwrap();
+
+        fs::write(skill.join("scripts").join("run.sh"), "echo bye").unwrap();
+        let after = tree_hash(&skill).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_ne!(before, after);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories_are_hashed_by_link_target() {
+        let tmp = unique_tmp("symlink");
+        let _ = fs::remove_dir_all(&tmp);
+        let skill = tmp.join("my-skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# My Skill").unwrap();
+        std::os::unix::fs::symlink("..", skill.join("parent")).unwrap();
+
+        let before = tree_hash(&skill).unwrap();
+        let files = file_hashes(&skill).unwrap();
+        fs::remove_file(skill.join("parent")).unwrap();
+        std::os::unix::fs::symlink("../..", skill.join("parent")).unwrap();
+        let after = tree_hash(&skill).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_ne!(before, after);
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["SKILL.md", "parent"]);
+        assert!(files["parent"].starts_with("l:sha256-"));
+    }
+
+    #[test]
+    fn file_hashes_list_nested_files_by_relative_path() {
+        let tmp = unique_tmp("files");
+        let _ = fs::remove_dir_all(&tmp);
+        let skill = tmp.join("my-skill");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(skill.join("SKILL.md"), "# My Skill").unwrap();
+        fs::write(skill.join("scripts").join("run.sh"), "echo hi").unwrap();
+
+        let files = file_hashes(&skill).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec!["SKILL.md", "scripts/run.sh"]
+        );
+        assert_eq!(
+            files["SKILL.md"],
+            format!("sha256-{}", sha256_hex(b"# My Skill"))
+        );
+    }
+}
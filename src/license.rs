@@ -0,0 +1,370 @@
+use crate::config::{InstallConfig, LicenseAction};
+use crate::validate::parse_frontmatter;
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+/// Identify a license text, returning its SPDX identifier.
+/// An explicit `SPDX-License-Identifier:` line wins over recognizing the text.
+pub fn identify(text: &str) -> Option<String> {
+    if let Some(id) = text.lines().find_map(|line| {
+        line.split_once("SPDX-License-Identifier:")
+            .map(|(_, id)| id.trim().trim_end_matches("*/").trim().to_string())
+    }) {
+        return Some(id).filter(|id| !id.is_empty());
+    }
+
+    let text = text
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let has = |needle: &str| text.contains(needle);
+    let id = if has("gnu affero general public license") {
+        "AGPL-3.0"
+    } else if has("gnu lesser general public license") {
+        if has("version 2.1") {
+            "LGPL-2.1"
+        } else {
+            "LGPL-3.0"
+        }
+    } else if has("gnu general public license") {
+        if has("version 2") && !has("version 3") {
+            "GPL-2.0"
+        } else {
+            "GPL-3.0"
+        }
+    } else if has("apache license") && has("version 2.0") {
+        "Apache-2.0"
+    } else if has("mozilla public license") && has("2.0") {
+        "MPL-2.0"
+    } else if has("eclipse public license") && has("2.0") {
+        "EPL-2.0"
+    } else if has("free and unencumbered software released into the public domain") {
+        "Unlicense"
+    } else if has("cc0 1.0") {
+        "CC0-1.0"
+    } else if has("attribution-noncommercial") && has("4.0") {
+        "CC-BY-NC-4.0"
+    } else if has("attribution-sharealike 4.0") {
+        "CC-BY-SA-4.0"
+    } else if has("attribution 4.0 international") {
+        "CC-BY-4.0"
+    } else if has("permission is hereby granted, free of charge") {
+        "MIT"
+    } else if has(
+        "permission to use, copy, modify, and/or distribute this software for any purpose",
+    ) {
+        "ISC"
+    } else if has("redistribution and use in source and binary forms") {
+        if has("neither the name") || has("may not be used to endorse") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        }
+    } else {
+        return None;
+    };
+    Some(id.to_string())
+}
+
+/// License files in `dir` (LICENSE, LICENCE, COPYING, or UNLICENSE, any extension), sorted.
+fn license_files(dir: &Path) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_uppercase())
+                .unwrap_or_default();
+            ["LICENSE", "LICENCE", "COPYING", "UNLICENSE"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Combine the licenses of several license files. Packages that ship more than one
+/// (`LICENSE-MIT` and `LICENSE-APACHE`) offer a choice, so the result is an OR expression.
+/// None if any file can't be identified, since the package's terms are then unclear.
+fn combined_license(files: &[std::path::PathBuf]) -> Option<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for file in files {
+        let id = fs::read_to_string(file)
+            .ok()
+            .and_then(|text| identify(&text))?;
+        if !ids.iter().any(|known| same_license(known, &id)) {
+            ids.push(id);
+        }
+    }
+    match ids.len() {
+        0 => None,
+        1 => ids.pop(),
+        _ => Some(
+            ids.iter()
+                .map(|id| {
+                    if id.contains(' ') {
+                        format!("({})", id)
+                    } else {
+                        id.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" OR "),
+        ),
+    }
+}
+
+/// License of a skill: its `license:` frontmatter, otherwise the license files of the
+/// nearest directory that has any, from the skill directory up to `package_root`. None if it
+/// can't be determined.
+pub fn skill_license(skill_dir: &Path, package_root: &Path) -> Option<String> {
+    let declared = fs::read_to_string(skill_dir.join("SKILL.md"))
+        .ok()
+        .and_then(|content| parse_frontmatter(&content))
+        .and_then(|meta| meta.license)
+        .filter(|license| !license.is_empty());
+    if declared.is_some() {
+        return declared;
+    }
+
+    let mut dir = Some(skill_dir);
+    while let Some(current) = dir {
+        let files = license_files(current);
+        if !files.is_empty() {
+            return combined_license(&files);
+        }
+        if current == package_root || !current.starts_with(package_root) {
+            break;
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// Compare SPDX identifiers case-insensitively, ignoring `-only`, `-or-later`, and `+`.
+fn same_license(a: &str, b: &str) -> bool {
+    let base = |id: &str| {
+        let id = id.trim().to_lowercase();
+        let id = id.trim_end_matches('+');
+        let id = id.strip_suffix("-or-later").unwrap_or(id);
+        id.strip_suffix("-only").unwrap_or(id).to_string()
+    };
+    base(a) == base(b)
+}
+
+/// Split an SPDX expression into alternatives (OR) of required terms (AND).
+/// Parentheses and `WITH` exceptions are ignored.
+fn alternatives(expression: &str) -> Vec<Vec<String>> {
+    let cleaned = expression.replace(['(', ')'], " ");
+    let mut alternatives = vec![Vec::new()];
+    let mut words = cleaned.split_whitespace();
+    while let Some(word) = words.next() {
+        match word.to_uppercase().as_str() {
+            "OR" => alternatives.push(Vec::new()),
+            "AND" => {}
+            "WITH" => {
+                words.next();
+            }
+            _ => alternatives
+                .last_mut()
+                .expect("alternatives is never empty")
+                .push(word.to_string()),
+        }
+    }
+    alternatives.retain(|terms| !terms.is_empty());
+    alternatives
+}
+
+/// Why a license breaks the configured policy, or None if it is acceptable.
+pub fn violation(license: Option<&str>, config: &InstallConfig) -> Option<String> {
+    if config.allowed_licenses.is_empty() && config.denied_licenses.is_empty() {
+        return None;
+    }
+    let Some(license) = license else {
+        return (!config.allowed_licenses.is_empty())
+            .then(|| "license could not be determined".to_string());
+    };
+
+    let listed = |list: &[String], term: &str| list.iter().any(|id| same_license(id, term));
+    let options = alternatives(license);
+    let usable: Vec<&Vec<String>> = options
+        .iter()
+        .filter(|terms| {
+            !terms
+                .iter()
+                .any(|term| listed(&config.denied_licenses, term))
+        })
+        .collect();
+    if usable.is_empty() {
+        return Some(format!("{} is denied", license));
+    }
+    if !config.allowed_licenses.is_empty()
+        && !usable.iter().any(|terms| {
+            terms
+                .iter()
+                .all(|term| listed(&config.allowed_licenses, term))
+        })
+    {
+        return Some(format!("{} is not in allowed_licenses", license));
+    }
+    None
+}
+
+/// Check (skill name, license) pairs against the license policy. Violations fail the install,
+/// or only print warnings when `on_license_violation` is "warn".
+pub fn enforce(skills: &[(&str, Option<&str>)], config: &InstallConfig) -> Result<()> {
+    let violations: Vec<String> = skills
+        .iter()
+        .filter_map(|(name, license)| {
+            violation(*license, config).map(|reason| format!("'{}': {}", name, reason))
+        })
+        .collect();
+    if violations.is_empty() {
+        return Ok(());
+    }
+    if config.on_license_violation == LicenseAction::Warn {
+        for violation in &violations {
+            eprintln!("Warning: license policy: {}", violation);
+        }
+        return Ok(());
+    }
+    anyhow::bail!(
+        "License policy blocks {} skill(s):\n  {}\nHint: Adjust install.allowed_licenses or install.denied_licenses in the config, or set install.on_license_violation to \"warn\".",
+        violations.len(),
+        violations.join("\n  ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_license_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn identifies_common_license_texts() {
+        let cases = [
+            (
+                "MIT License\n\nPermission is hereby granted, free of charge, to any person",
+                "MIT",
+            ),
+            ("Apache License\n  Version 2.0, January 2004", "Apache-2.0"),
+            (
+                "GNU LESSER GENERAL PUBLIC LICENSE\nVersion 2.1, February 1999",
+                "LGPL-2.1",
+            ),
+            (
+                "GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007",
+                "GPL-3.0",
+            ),
+            (
+                "Redistribution and use in source and binary forms ... Neither the name of",
+                "BSD-3-Clause",
+            ),
+            (
+                "This is free and unencumbered software released into the public domain.",
+                "Unlicense",
+            ),
+            ("// SPDX-License-Identifier: MPL-2.0", "MPL-2.0"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(identify(text).as_deref(), Some(expected), "{}", text);
+        }
+        assert_eq!(identify("All rights reserved."), None);
+    }
+
+    #[test]
+    fn frontmatter_license_overrides_package_license_file() {
+        let tmp = unique_tmp("skill");
+        let _ = fs::remove_dir_all(&tmp);
+        let declared = tmp.join("skills").join("declared");
+        let inherited = tmp.join("skills").join("inherited");
+        fs::create_dir_all(&declared).unwrap();
+        fs::create_dir_all(&inherited).unwrap();
+        fs::write(tmp.join("LICENSE.md"), "Apache License\nVersion 2.0").unwrap();
+        fs::write(
+            declared.join("SKILL.md"),
+            "---\nname: declared\nlicense: MIT\n---\n",
+        )
+        .unwrap();
+        fs::write(inherited.join("SKILL.md"), "---\nname: inherited\n---\n").unwrap();
+
+        let declared_license = skill_license(&declared, &tmp);
+        let inherited_license = skill_license(&inherited, &tmp);
+        let outside_root = skill_license(&inherited, &tmp.join("skills"));
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(declared_license.as_deref(), Some("MIT"));
+        assert_eq!(inherited_license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(outside_root, None);
+    }
+
+    #[test]
+    fn multiple_license_files_are_combined() {
+        let tmp = unique_tmp("dual");
+        let _ = fs::remove_dir_all(&tmp);
+        let (dual, unclear) = (tmp.join("dual"), tmp.join("unclear"));
+        for dir in [&dual, &unclear] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(
+                dir.join("LICENSE-MIT"),
+                "Permission is hereby granted, free of charge",
+            )
+            .unwrap();
+            fs::write(dir.join("LICENSE-APACHE"), "Apache License\nVersion 2.0").unwrap();
+        }
+        fs::write(dual.join("COPYING"), "SPDX-License-Identifier: MIT").unwrap();
+        fs::write(unclear.join("LICENSE.txt"), "All rights reserved.").unwrap();
+
+        let dual_license = skill_license(&dual, &dual);
+        let unclear_license = skill_license(&unclear, &unclear);
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(dual_license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(unclear_license, None);
+    }
+
+    #[test]
+    fn policy_handles_allow_deny_lists_and_expressions() {
+        let config = InstallConfig {
+            allowed_licenses: vec!["MIT".to_string(), "Apache-2.0".to_string()],
+            denied_licenses: vec!["GPL-3.0".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(violation(Some("mit"), &config), None);
+        assert_eq!(violation(Some("GPL-3.0-or-later OR MIT"), &config), None);
+        assert!(violation(Some("GPL-3.0-only"), &config)
+            .unwrap()
+            .contains("denied"));
+        assert!(violation(Some("MIT AND BSD-3-Clause"), &config)
+            .unwrap()
+            .contains("not in allowed_licenses"));
+        assert!(violation(None, &config).is_some());
+
+        let deny_only = InstallConfig {
+            denied_licenses: vec!["AGPL-3.0".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(violation(None, &deny_only), None);
+        assert!(enforce(&[("a", Some("AGPL-3.0"))], &deny_only).is_err());
+        let warn_only = InstallConfig {
+            on_license_violation: LicenseAction::Warn,
+            ..deny_only
+        };
+        assert!(enforce(&[("a", Some("AGPL-3.0"))], &warn_only).is_ok());
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LOCKFILE_NAME: &str = "skillset.lock";
+const LOCKFILE_VERSION: u32 = 1;
+
+/// Provenance of a single installed skill.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LockedSkill {
+    /// Package spec as given to `skillset install`
+    pub package: String,
+    /// Branch, tag, or commit requested for the package (absent for the default branch)
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Resolved Git remote or archive download URL (absent for local path installs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Commit SHA the skill was installed from (absent for non-Git sources)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Skill directory relative to the package root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// Skill name in the package, when installed under another name (`--as` or `--prefix`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+    /// SPDX license of the skill (frontmatter `license:` or the package's LICENSE file)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Content hash of the installed skill directory (see `hash::tree_hash`)
+    pub hash: String,
+    /// Per-file hashes of the installed copy (see `hash::file_hashes`), used by `verify`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+}
+
+/// Lockfile maps installed skill name -> LockedSkill
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default)]
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+/// Lockfile path for a source directory: `.skillset/skills` -> `.skillset/skillset.lock`.
+pub fn lock_path(source_dir: &Path) -> PathBuf {
+    source_dir
+        .parent()
+        .unwrap_or(source_dir)
+        .join(LOCKFILE_NAME)
+}
+
+pub fn load(source_dir: &Path) -> Result<Lockfile> {
+    let path = lock_path(source_dir);
+    if !path.exists() {
+        return Ok(Lockfile::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read lockfile: {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| {
+        format!(
+            "Failed to parse lockfile: {}\nHint: Fix or delete the file and re-run `skillset install`.",
+            path.display()
+        )
+    })
+}
+
+pub fn save(source_dir: &Path, lockfile: &Lockfile) -> Result<()> {
+    let path = lock_path(source_dir);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create lockfile directory")?;
+    }
+    let mut content =
+        serde_json::to_string_pretty(lockfile).context("Failed to serialize lockfile")?;
+    content.push('\n');
+    fs::write(&path, content).context("Failed to write lockfile")?;
+    Ok(())
+}
+
+/// Record (or replace) entries for installed skills and write the lockfile.
+pub fn record(source_dir: &Path, entries: Vec<(String, LockedSkill)>) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut lockfile = load(source_dir)?;
+    for (name, entry) in entries {
+        lockfile.skills.insert(name, entry);
+    }
+    save(source_dir, &lockfile)
+}
+
+impl Lockfile {
+    /// Commit previously installed for a package, if all its skills agree on one.
+    pub fn locked_commit(&self, package: &str, git_ref: Option<&str>) -> Option<&str> {
+        let mut commits = self
+            .skills
+            .values()
+            .filter(|s| s.package == package && s.git_ref.as_deref() == git_ref)
+            .map(|s| s.commit.as_deref());
+        let first = commits.next()??;
+        commits.all(|c| c == Some(first)).then_some(first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_lockfile_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn entry(package: &str) -> LockedSkill {
+        LockedSkill {
+            package: package.to_string(),
+            git_ref: None,
+            url: Some(format!("https://github.com/{}.git", package)),
+            commit: Some("0123456789abcdef".to_string()),
+            subdir: Some("skills/my-skill".to_string()),
+            skill: None,
+            license: None,
+            hash: "sha256-abc".to_string(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn lock_path_sits_next_to_source_dir() {
+        assert_eq!(
+            lock_path(Path::new("/workspace/.skillset/skills")),
+            PathBuf::from("/workspace/.skillset/skillset.lock")
+        );
+    }
+
+    #[test]
+    fn record_round_trips_and_replaces_entries() {
+        let tmp = unique_tmp("round_trip");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join(".skillset").join("skills");
+
+        record(&source, vec![("my-skill".to_string(), entry("a/b"))]).unwrap();
+        record(&source, vec![("my-skill".to_string(), entry("c/d"))]).unwrap();
+        let lockfile = load(&source).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(lockfile.version, LOCKFILE_VERSION);
+        assert_eq!(lockfile.skills.len(), 1);
+        assert_eq!(lockfile.skills["my-skill"], entry("c/d"));
+    }
+
+    #[test]
+    fn locked_commit_requires_matching_ref_and_agreement() {
+        let mut lockfile = Lockfile::default();
+        lockfile.skills.insert("one".to_string(), entry("a/b"));
+        lockfile.skills.insert("two".to_string(), entry("a/b"));
+        assert_eq!(
+            lockfile.locked_commit("a/b", None),
+            Some("0123456789abcdef")
+        );
+        assert_eq!(lockfile.locked_commit("a/b", Some("v1")), None);
+
+        let mut other = entry("a/b");
+        other.commit = Some("fedcba9876543210".to_string());
+        lockfile.skills.insert("three".to_string(), other);
+        assert_eq!(lockfile.locked_commit("a/b", None), None);
+    }
+}
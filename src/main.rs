@@ -5,20 +5,37 @@ use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 
 mod add;
+mod adopt;
+mod archive;
+mod cache;
 mod config;
+mod deps;
 mod doc;
+mod hash;
 mod install;
+mod license;
+mod lockfile;
+mod managed;
+mod manifest;
 mod path;
+mod preview;
+mod reconcile;
 mod registry;
 mod remove;
+mod scan;
+mod signature;
 mod skills;
+mod sparse;
+mod update;
 mod validate;
+mod verify;
 mod version_check;
 
 use config::{config_path, load, supported_tools};
 use doc::agents_md_snippet;
+use managed::Ownership;
 use path::resolve_source;
-use skills::{discover_skills, sync_skills, OverwritePolicy, SyncMethod};
+use skills::{discover_skills, prune_skills, sync_skills, OverwritePolicy, SyncMethod};
 
 #[derive(Parser)]
 #[command(name = "skillset", version)]
@@ -40,6 +57,15 @@ struct Cli {
     #[arg(long, global = true)]
     dry_run: bool,
 
+    /// Never touch the network: resolve packages only from the cache and lockfile
+    #[arg(
+        long,
+        global = true,
+        env = "SKILLSET_OFFLINE",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    offline: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -60,34 +86,70 @@ enum Commands {
     },
     /// Sync skills from source to configured targets
     Sync {
-        /// Show diff of SKILL.md before overwriting
+        /// Show a diff of every changed file before overwriting
         #[arg(long)]
         diff: bool,
         /// Symlink skills into targets (default; retained for backward compatibility)
         #[arg(long)]
         symlink: bool,
+        /// Copy skills into targets instead of symlinking them
+        #[arg(long, conflicts_with = "symlink")]
+        copy: bool,
+        /// Also remove skills skillset synced earlier that are no longer in the source
+        #[arg(long)]
+        prune: bool,
     },
     /// Convert configured target skills to symlinks pointing at the source
     #[command(name = "migrate-to-symlinked")]
     MigrateToSymlinked {
-        /// Show diff of SKILL.md before overwriting
+        /// Show a diff of every changed file before overwriting
         #[arg(long)]
         diff: bool,
     },
     /// Convert configured target skills back to copied directories
     #[command(name = "migrate-to-copy")]
     MigrateToCopy {
-        /// Show diff of SKILL.md before overwriting
+        /// Show a diff of every changed file before overwriting
         #[arg(long)]
         diff: bool,
     },
-    /// Install skills from a local path, owner/repo package, or full Git URL
+    /// Copy edits made to a skill inside a tool's directory back into the source
+    Pull {
+        /// Skill name
+        skill: String,
+        /// Tool to pull from (matched against target labels, e.g. cursor or claude)
+        #[arg(long)]
+        from: String,
+    },
+    /// Import skills found in tool directories into the source
+    Adopt {
+        /// Skills to adopt: names or glob patterns (omit for every unmanaged skill)
+        skills: Vec<String>,
+        /// Only adopt from this tool (matched against target labels, e.g. cursor or claude)
+        #[arg(long)]
+        from: Option<String>,
+        /// Replace the originals with symlinks to the adopted source skills
+        #[arg(long)]
+        link: bool,
+    },
+    /// Install skills from a local path, owner/repo package, or full Git URL.
+    /// Without a package, restore every package listed in .skillset/skillset.json
     Install {
-        /// Package spec as a local path, owner/repo format, or full Git URL
-        package: String,
-        /// Install only the specified skill
+        /// Package spec as a local path, owner/repo format, or full Git URL (omit to install from the manifest)
+        package: Option<String>,
+        /// Install only these skills: comma-separated names or glob patterns (e.g. pdf,frontend-*)
         #[arg(long)]
         skill: Option<String>,
+        /// Skip these skills: comma-separated names or glob patterns (`--exclude=` clears
+        /// the exclusions saved in the manifest)
+        #[arg(long)]
+        exclude: Option<String>,
+        /// Install the selected skill under a different name (rewrites its `name:` frontmatter)
+        #[arg(long = "as", value_name = "NAME")]
+        install_as: Option<String>,
+        /// Prepend a namespace to every installed skill name (e.g. --prefix acme-)
+        #[arg(long, conflicts_with = "install_as")]
+        prefix: Option<String>,
         /// After installing, sync skills from source to configured targets
         #[arg(long)]
         sync: bool,
@@ -100,7 +162,25 @@ enum Commands {
         /// Pull latest from remote before installing (refreshes cached repo)
         #[arg(long)]
         from_remote: bool,
+        /// Git branch, tag, or commit to install (same as appending @<ref> to the package)
+        #[arg(long = "ref", value_name = "REF")]
+        git_ref: Option<String>,
+        /// Refuse packages without a signature from a trusted key (see install.signing in config)
+        #[arg(long)]
+        require_signed: bool,
     },
+    /// Show installed skills whose upstream package has changed since install
+    Outdated,
+    /// Pull upstream changes and re-install only the skills that changed
+    Update {
+        /// Skill name or package spec to update (omit for all installed packages)
+        name: Option<String>,
+        /// Refuse upstream packages without a signature from a trusted key
+        #[arg(long)]
+        require_signed: bool,
+    },
+    /// Check installed skills against the hashes recorded in the lockfile (non-zero exit on changes)
+    Verify,
     /// Add/scaffold a new skill
     Add {
         /// Name of the skill to create
@@ -117,6 +197,18 @@ enum Commands {
         #[arg(long)]
         yes: bool,
     },
+    /// Remove every skill a package installed, from the source and matching targets
+    Uninstall {
+        /// Package spec as recorded in the lockfile (e.g. anthropics/skills)
+        package: String,
+        /// Skip confirmation prompts and uninstall even if skills outside the package still
+        /// require it (skills with local changes are kept)
+        #[arg(long)]
+        yes: bool,
+        /// Also remove skills with local changes
+        #[arg(long)]
+        remove_modified: bool,
+    },
     /// Validate skills in source directory (check SKILL.md frontmatter)
     Validate,
     /// Show where skills are installed (tracked instances)
@@ -127,6 +219,11 @@ enum Commands {
         #[arg(long)]
         scan: bool,
     },
+    /// Manage cached package repos (list, prune, clean, path)
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
     /// Manage configuration (show, add/remove targets, reset)
     Config {
         #[command(subcommand)]
@@ -148,6 +245,18 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum CacheAction {
+    /// List cached repos with their spec, URL, size, commit, and last use
+    List,
+    /// Remove cached repos that no manifest or lockfile references
+    Prune,
+    /// Remove every cached repo and archive
+    Clean,
+    /// Print the cache directory
+    Path,
+}
+
 #[derive(Subcommand)]
 enum ConfigAction {
     /// Show current configuration
@@ -175,6 +284,7 @@ fn main() -> Result<()> {
 
     let force = cli.force || cli.yes;
     let dry_run = cli.dry_run;
+    let offline = cli.offline;
     let is_self_update = matches!(cli.command, Commands::SelfUpdate);
 
     match cli.command {
@@ -183,42 +293,84 @@ fn main() -> Result<()> {
             filter,
             status,
         } => list_skills(cli.user, tool.as_deref(), filter.as_deref(), &status)?,
-        Commands::Sync { diff, symlink } => sync_skills_cli(
+        Commands::Sync {
+            diff,
+            symlink,
+            copy,
+            prune,
+        } => sync_skills_cli(
             cli.user,
             force,
             dry_run,
             diff,
-            sync_method_from_flag(symlink),
+            if copy {
+                SyncMethod::Copy
+            } else {
+                sync_method_from_flag(symlink)
+            },
+            prune,
         )?,
         Commands::MigrateToSymlinked { diff } => {
-            sync_skills_cli(cli.user, force, dry_run, diff, SyncMethod::Symlink)?
+            sync_skills_cli(cli.user, force, dry_run, diff, SyncMethod::Symlink, false)?
         }
         Commands::MigrateToCopy { diff } => {
-            sync_skills_cli(cli.user, force, dry_run, diff, SyncMethod::Copy)?
+            sync_skills_cli(cli.user, force, dry_run, diff, SyncMethod::Copy, false)?
+        }
+        Commands::Pull { skill, from } => pull_skill(&skill, &from, cli.user, force, dry_run)?,
+        Commands::Adopt { skills, from, link } => {
+            adopt_skills(&skills, from.as_deref(), link, cli.user, force, dry_run)?
         }
         Commands::Install {
             package,
             skill,
+            exclude,
+            install_as,
+            prefix,
             sync,
             dir,
             from_remote,
             symlink,
+            git_ref,
+            require_signed,
         } => install_package(
             package,
             skill.as_deref(),
+            exclude.as_deref(),
+            install_as,
+            prefix,
+            git_ref,
+            require_signed,
             cli.user,
             sync,
             force,
             dir.as_deref(),
-            from_remote,
+            fetch_mode(from_remote, offline)?,
             dry_run,
             sync_method_from_flag(symlink),
         )?,
+        Commands::Outdated => outdated(cli.user, offline)?,
+        Commands::Update {
+            name,
+            require_signed,
+        } => update(
+            cli.user,
+            name.as_deref(),
+            require_signed,
+            force,
+            dry_run,
+            offline,
+        )?,
+        Commands::Verify => verify_skills(cli.user)?,
         Commands::Add {
             name,
             force: cmd_force,
         } => add_skill(name, cli.user, cmd_force || force)?,
         Commands::Remove { name, yes } => remove_skill(name, cli.user, yes || force)?,
+        Commands::Uninstall {
+            package,
+            yes,
+            remove_modified,
+        } => uninstall_package(&package, cli.user, yes || force, remove_modified, dry_run)?,
         Commands::Validate => validate_skills(cli.user)?,
         Commands::Where { skill, scan } => {
             if scan {
@@ -226,6 +378,7 @@ fn main() -> Result<()> {
             }
             where_skills(skill.as_deref())?;
         }
+        Commands::Cache { action } => cache_command(action, dry_run)?,
         Commands::Config { action } => config_command(action)?,
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
@@ -235,7 +388,7 @@ fn main() -> Result<()> {
         Commands::Doc { agents_md } => doc_output(agents_md)?,
     }
 
-    if !is_self_update {
+    if !is_self_update && !offline {
         version_check::check_and_notify();
     }
 
@@ -258,7 +411,7 @@ fn list_skills(
 
     match tool {
         Some(tool_name) => {
-            list_skills_for_tool(&config, tool_name, user_scope, &cwd)?;
+            list_skills_for_tool(&config, &source, tool_name, user_scope, &cwd)?;
         }
         None => {
             list_skills_with_status(&config, &source, user_scope, &cwd, filter, status)?;
@@ -304,7 +457,12 @@ fn list_skills_with_status(
         for (label, target_path) in &targets {
             let skill_path = target_path.join(skill);
             if skill_path.exists() {
-                statuses.push(format!("{} ✓", label));
+                let mark = match managed::ownership(target_path, skill, source) {
+                    Ownership::Managed => "✓",
+                    Ownership::Modified => "~",
+                    Ownership::Foreign => "?",
+                };
+                statuses.push(format!("{} {}", label, mark));
                 synced_count += 1;
             } else {
                 statuses.push(format!("{} —", label));
@@ -322,12 +480,14 @@ fn list_skills_with_status(
             println!("  {}  {}", skill, statuses.join("  "));
         }
     }
+    println!("\n✓ synced  ~ changed in the target since the last sync  ? not managed by skillset  — missing");
 
     Ok(())
 }
 
 fn list_skills_for_tool(
     config: &config::Config,
+    source: &Path,
     tool: &str,
     user_scope: bool,
     cwd: &Path,
@@ -375,7 +535,12 @@ fn list_skills_for_tool(
 
         println!("Skills:");
         for skill in tool_skills {
-            println!("  {}", skill);
+            let note = match managed::ownership(&path, &skill, source) {
+                Ownership::Managed => "",
+                Ownership::Modified => "  (changed here since the last sync)",
+                Ownership::Foreign => "  (not managed by skillset)",
+            };
+            println!("  {}{}", skill, note);
         }
         println!();
     }
@@ -496,6 +661,7 @@ fn sync_skills_cli(
     dry_run: bool,
     show_diff: bool,
     method: SyncMethod,
+    prune: bool,
 ) -> Result<()> {
     let config = load()?;
     let cwd = std::env::current_dir()?;
@@ -545,47 +711,122 @@ fn sync_skills_cli(
         show_diff,
         method,
     )?;
+    if prune {
+        println!();
+        // Prune asks again even if "all" was answered during the sync above.
+        let mut prune_policy = if force || dry_run {
+            OverwritePolicy::All
+        } else {
+            OverwritePolicy::PerSkill
+        };
+        prune_skills(&source, &selected, &mut prune_policy, dry_run)?;
+    }
 
     Ok(())
 }
 
+/// How install may reach the network: `--from-remote` refreshes caches, `--offline` forbids it.
+fn fetch_mode(from_remote: bool, offline: bool) -> Result<install::Fetch> {
+    match (from_remote, offline) {
+        (true, true) => anyhow::bail!(
+            "--from-remote needs network access and can't be combined with --offline.\nHint: Drop --offline (or unset SKILLSET_OFFLINE) to pull the latest."
+        ),
+        (true, false) => Ok(install::Fetch::Refresh),
+        (false, true) => Ok(install::Fetch::Offline),
+        (false, false) => Ok(install::Fetch::IfMissing),
+    }
+}
+
+/// Split a comma-separated CLI list, dropping empty items.
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 #[allow(clippy::too_many_arguments)]
 fn install_package(
-    package: String,
+    package: Option<String>,
     skill: Option<&str>,
+    exclude: Option<&str>,
+    install_as: Option<String>,
+    prefix: Option<String>,
+    git_ref: Option<String>,
+    require_signed: bool,
     user_scope: bool,
     do_sync: bool,
     force: bool,
     dir: Option<&str>,
-    from_remote: bool,
+    fetch: install::Fetch,
     dry_run: bool,
     sync_method: SyncMethod,
 ) -> Result<()> {
     let config = load()?;
     let cwd = std::env::current_dir()?;
-
-    if dry_run {
-        println!("[DRY RUN] Would install from package: {}", package);
-        if let Some(s) = skill {
-            println!("[DRY RUN] Filtered to skill: {}", s);
+    let scope_source = resolve_source(user_scope, &cwd, &config.source);
+
+    // With a package argument install just that package; without one restore the manifest.
+    let entries = match &package {
+        Some(spec) => {
+            let mut request = install::package_request(spec, git_ref, split_list(skill))?;
+            request.exclude = split_list(exclude);
+            request.install_as = install_as;
+            request.prefix = prefix;
+            let saved = manifest::load(&scope_source)?
+                .packages
+                .into_iter()
+                .find(|entry| {
+                    entry.package == request.package && entry.install_as == request.install_as
+                });
+            // Reinstalling without --exclude keeps the saved exclusions (`--exclude=` clears them).
+            if let Some(saved) = saved.as_ref().filter(|_| exclude.is_none()) {
+                if !saved.exclude.is_empty() {
+                    println!(
+                        "Using saved exclusions for {}: {}",
+                        request.package,
+                        saved.exclude.join(", ")
+                    );
+                    request.exclude = saved.exclude.clone();
+                }
+            }
+            // Reinstalling a prefixed package without --prefix keeps its namespace.
+            if request.prefix.is_none() && request.install_as.is_none() {
+                request.prefix = saved.and_then(|entry| entry.prefix);
+                if let Some(prefix) = &request.prefix {
+                    println!("Using saved prefix '{}' for {}", prefix, request.package);
+                }
+            }
+            vec![request]
         }
-        let scope = if user_scope {
-            "user store"
-        } else {
-            "workspace"
-        };
-        println!("[DRY RUN] Target scope: {}", scope);
-        if do_sync {
-            let targets = targets_for_scope(&config.targets, &cwd, user_scope);
-            println!("[DRY RUN] Would sync to {} target(s):", targets.len());
-            for (label, path) in &targets {
-                println!("[DRY RUN]   {} ({})", label, path.display());
+        None => {
+            if skill.is_some()
+                || exclude.is_some()
+                || install_as.is_some()
+                || prefix.is_some()
+                || git_ref.is_some()
+            {
+                anyhow::bail!("--skill, --exclude, --as, --prefix, and --ref require a package argument.\nHint: Run `skillset install <package> --skill=<name>`.");
+            }
+            let manifest = manifest::load(&scope_source)?;
+            if manifest.packages.is_empty() {
+                anyhow::bail!(
+                    "No packages listed in {}\nHint: Run `skillset install <package>` to install a package and add it to the manifest.",
+                    manifest::manifest_path(&scope_source).display()
+                );
             }
-            println!("[DRY RUN] Sync method: {:?}", sync_method);
+            println!(
+                "Installing {} package(s) from {}",
+                manifest.packages.len(),
+                manifest::manifest_path(&scope_source).display()
+            );
+            manifest.packages
         }
-        println!("[DRY RUN] No changes made.");
-        return Ok(());
-    }
+    };
 
     // --sync: targets filtered by scope (workspace vs user)
     let targets = targets_for_scope(&config.targets, &cwd, user_scope);
@@ -603,25 +844,63 @@ fn install_package(
         None
     };
 
-    let skill_dirs: Vec<String> = if let Some(d) = dir {
-        d.split(',')
+    let mut install_config = config.install.clone();
+    if let Some(d) = dir {
+        install_config.skill_dirs = d
+            .split(',')
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
-            .collect()
-    } else {
-        config.install.skill_dirs.clone()
+            .collect();
+    }
+    install_config.signing.require_signed |= require_signed;
+
+    // Restoring from the manifest reinstalls the commits recorded in the lockfile;
+    // --from-remote (or an explicit package) resolves refs to their latest commit instead.
+    let lock = lockfile::load(&scope_source)?;
+    // A dry run resolves and inspects each package, then reports instead of copying.
+    let preview = install::DryRun {
+        sync: do_sync.then_some((targets.as_slice(), sync_method)),
     };
+    let mut installed = Vec::new();
+    for entry in &entries {
+        let locked_commit = if package.is_none() && fetch != install::Fetch::Refresh {
+            lock.locked_commit(&entry.package, entry.git_ref.as_deref())
+        } else {
+            None
+        };
+        // Offer the skill picker only for an explicit package, never when restoring or with --force.
+        installed.extend(install::install_package(
+            entry,
+            locked_commit,
+            source_dir.as_deref(),
+            user_store_dir.as_deref(),
+            force,
+            &install_config,
+            fetch,
+            package.is_some() && !force,
+            dry_run.then_some(&preview),
+        )?);
+    }
 
-    install::install_package(
-        &package,
-        skill,
-        source_dir.as_deref(),
-        user_store_dir.as_deref(),
-        force,
-        config.install.use_ssh,
-        &skill_dirs,
-        from_remote,
-    )?;
+    if dry_run {
+        println!("[DRY RUN] No changes made.");
+        return Ok(());
+    }
+
+    // Record explicitly installed packages so `skillset install` can restore them later.
+    if let Some(entry) = installed.into_iter().next().filter(|_| package.is_some()) {
+        let mut manifest = manifest::load(&scope_source)?;
+        let cleared = exclude.is_some()
+            && entry.exclude.is_empty()
+            && manifest.clear_exclude(&entry.package, entry.install_as.as_deref());
+        if manifest.add(entry) || cleared {
+            manifest::save(&scope_source, &manifest)?;
+            println!(
+                "Added to manifest: {}",
+                manifest::manifest_path(&scope_source).display()
+            );
+        }
+    }
 
     if !do_sync {
         if user_scope {
@@ -668,6 +947,35 @@ fn install_package(
     Ok(())
 }
 
+fn outdated(user_scope: bool, offline: bool) -> Result<()> {
+    let config = load()?;
+    let cwd = std::env::current_dir()?;
+    let source = resolve_source(user_scope, &cwd, &config.source);
+    update::outdated(&source, &config.install, offline)
+}
+
+fn update(
+    user_scope: bool,
+    name: Option<&str>,
+    require_signed: bool,
+    force: bool,
+    dry_run: bool,
+    offline: bool,
+) -> Result<()> {
+    let mut config = load()?;
+    let cwd = std::env::current_dir()?;
+    let source = resolve_source(user_scope, &cwd, &config.source);
+    config.install.signing.require_signed |= require_signed;
+    update::update(&source, name, force, dry_run, &config.install, offline)
+}
+
+fn verify_skills(user_scope: bool) -> Result<()> {
+    let config = load()?;
+    let cwd = std::env::current_dir()?;
+    let source = resolve_source(user_scope, &cwd, &config.source);
+    verify::verify(&source)
+}
+
 fn add_skill(name: String, user_scope: bool, force: bool) -> Result<()> {
     let config = load()?;
     let cwd = std::env::current_dir()?;
@@ -688,6 +996,82 @@ fn remove_skill(name: String, user_scope: bool, yes: bool) -> Result<()> {
     Ok(())
 }
 
+fn uninstall_package(
+    package: &str,
+    user_scope: bool,
+    yes: bool,
+    remove_modified: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let config = load()?;
+    let cwd = std::env::current_dir()?;
+
+    let targets = targets_for_scope(&config.targets, &cwd, user_scope);
+    let source = resolve_source(user_scope, &cwd, &config.source);
+
+    remove::uninstall_package(package, &targets, &source, yes, remove_modified, dry_run)
+}
+
+/// The target whose label matches `tool` (case-insensitive substring, exact match preferred).
+fn find_target(targets: &[(String, PathBuf)], tool: &str) -> Result<(String, PathBuf)> {
+    let tool_lower = tool.to_lowercase();
+    let matches: Vec<&(String, PathBuf)> = targets
+        .iter()
+        .filter(|(label, _)| label.to_lowercase().contains(&tool_lower))
+        .collect();
+    if let Some(exact) = matches
+        .iter()
+        .find(|(label, _)| label.eq_ignore_ascii_case(tool))
+    {
+        return Ok((*exact).clone());
+    }
+    match matches.as_slice() {
+        [only] => Ok((*only).clone()),
+        [] => anyhow::bail!(
+            "Unknown tool '{}'.\nHint: Run `skillset list` to see the configured targets.",
+            tool
+        ),
+        _ => anyhow::bail!(
+            "'{}' matches several targets: {}\nHint: Use the full label.",
+            tool,
+            matches
+                .iter()
+                .map(|(label, _)| label.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn pull_skill(skill: &str, tool: &str, user_scope: bool, force: bool, dry_run: bool) -> Result<()> {
+    let config = load()?;
+    let cwd = std::env::current_dir()?;
+    let source = resolve_source(user_scope, &cwd, &config.source);
+    let targets = targets_for_scope(&config.targets, &cwd, user_scope);
+    let (label, path) = find_target(&targets, tool)?;
+
+    reconcile::pull(skill, &source, (&label, &path), force, dry_run)
+}
+
+fn adopt_skills(
+    skills: &[String],
+    tool: Option<&str>,
+    link: bool,
+    user_scope: bool,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let config = load()?;
+    let cwd = std::env::current_dir()?;
+    let source = resolve_source(user_scope, &cwd, &config.source);
+    let mut targets = targets_for_scope(&config.targets, &cwd, user_scope);
+    if let Some(tool) = tool {
+        targets = vec![find_target(&targets, tool)?];
+    }
+
+    adopt::adopt(&source, &targets, skills, link, force, dry_run)
+}
+
 fn validate_skills(user_scope: bool) -> Result<()> {
     let config = load()?;
     let cwd = std::env::current_dir()?;
@@ -746,6 +1130,25 @@ fn where_skills(skill: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+fn cache_command(action: CacheAction, dry_run: bool) -> Result<()> {
+    match action {
+        CacheAction::List => cache::list()?,
+        CacheAction::Prune => {
+            // Besides each repo's recorded users, always consider the current workspace and user store.
+            let config = load()?;
+            let cwd = std::env::current_dir()?;
+            let sources = [
+                resolve_source(false, &cwd, &config.source),
+                resolve_source(true, &cwd, &config.source),
+            ];
+            cache::prune(&sources, &config.install, dry_run)?;
+        }
+        CacheAction::Clean => cache::clean(dry_run)?,
+        CacheAction::Path => println!("{}", cache::cache_root()?.display()),
+    }
+    Ok(())
+}
+
 fn config_command(action: ConfigAction) -> Result<()> {
     match action {
         ConfigAction::Show => {
@@ -755,7 +1158,60 @@ fn config_command(action: ConfigAction) -> Result<()> {
             println!("Source: {}\n", config.source);
             println!("Install:");
             println!("  use_ssh: {}", config.install.use_ssh);
-            println!("  skill_dirs: {}\n", config.install.skill_dirs.join(", "));
+            println!("  skill_dirs: {}", config.install.skill_dirs.join(", "));
+            if !config.install.hosts.is_empty() {
+                println!("  hosts:");
+                for (name, alias) in &config.install.hosts {
+                    let protocol = if alias.use_ssh(config.install.use_ssh) {
+                        "ssh"
+                    } else {
+                        "https"
+                    };
+                    println!("    {}: {} ({})", name, alias.host(), protocol);
+                }
+            }
+            if !config.install.allowed_licenses.is_empty() {
+                println!(
+                    "  allowed_licenses: {}",
+                    config.install.allowed_licenses.join(", ")
+                );
+            }
+            if !config.install.denied_licenses.is_empty() {
+                println!(
+                    "  denied_licenses: {}",
+                    config.install.denied_licenses.join(", ")
+                );
+            }
+            let signing = &config.install.signing;
+            println!("  require_signed: {}", signing.require_signed);
+            if signing.trusted_keys.is_empty() {
+                println!("  trusted_keys: (none)");
+            } else {
+                println!("  trusted_keys:");
+                for (pattern, keys) in &signing.trusted_keys {
+                    println!("    {}: {} key(s)", pattern, keys.len());
+                }
+            }
+            let scan = &config.install.scan;
+            if scan.enabled {
+                println!(
+                    "  scan: on (binaries over {} KiB flagged)",
+                    scan.max_binary_kb
+                );
+                if !scan.disabled_rules.is_empty() {
+                    println!("    disabled_rules: {}", scan.disabled_rules.join(", "));
+                }
+                if !scan.patterns.is_empty() {
+                    println!("    patterns: {}", scan.patterns.join(", "));
+                }
+                if !scan.ignore.is_empty() {
+                    println!("    ignore: {}", scan.ignore.join(", "));
+                }
+            } else {
+                println!("  scan: off");
+            }
+            println!("  partial_clone: {}", config.install.partial_clone);
+            println!();
             println!("Targets ({}):", config.targets.len());
             for target in &config.targets {
                 let expanded = config::expand_home(&target.path);
@@ -929,4 +1385,12 @@ mod tests {
         assert!(matches!(sync_method_from_flag(false), SyncMethod::Symlink));
         assert!(matches!(sync_method_from_flag(true), SyncMethod::Symlink));
     }
+
+    #[test]
+    fn offline_mode_forbids_from_remote() {
+        assert_eq!(fetch_mode(false, true).unwrap(), install::Fetch::Offline);
+        assert_eq!(fetch_mode(true, false).unwrap(), install::Fetch::Refresh);
+        assert_eq!(fetch_mode(false, false).unwrap(), install::Fetch::IfMissing);
+        assert!(fetch_mode(true, true).is_err());
+    }
 }
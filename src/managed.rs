@@ -0,0 +1,266 @@
+use crate::hash::tree_hash;
+use crate::registry::now_timestamp;
+use crate::skills::SyncMethod;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Ownership manifest kept in every target directory `sync` writes to.
+pub const MANAGED_FILE: &str = ".skillset-managed.json";
+const MANAGED_VERSION: u32 = 1;
+
+/// A skill that `sync` wrote into a target.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ManagedSkill {
+    /// Source directory the skill was synced from (e.g. `/work/app/.skillset/skills`)
+    pub source: String,
+    /// Whether the skill was symlinked or copied
+    pub method: SyncMethod,
+    /// Content hash of the source skill at sync time (see `hash::tree_hash`)
+    pub hash: String,
+    pub synced_at: String,
+    /// Set when the user chose to keep the target's edits over the source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kept: Option<Kept>,
+}
+
+/// Target and source hashes at the time a diverged copy was kept, so `sync` asks again only
+/// once either side changes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Kept {
+    pub target: String,
+    pub source: String,
+}
+
+/// Manifest maps skill name -> ManagedSkill
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Managed {
+    pub version: u32,
+    #[serde(default)]
+    pub skills: BTreeMap<String, ManagedSkill>,
+}
+
+impl Default for Managed {
+    fn default() -> Self {
+        Self {
+            version: MANAGED_VERSION,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+/// How a skill in a target relates to the same-named skill in a source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ownership {
+    /// Synced from this source and untouched since
+    Managed,
+    /// Synced from this source, but changed in the target afterwards
+    Modified,
+    /// Added to the tool directly, or synced from another source
+    Foreign,
+}
+
+pub fn manifest_path(target_dir: &Path) -> PathBuf {
+    target_dir.join(MANAGED_FILE)
+}
+
+pub fn load(target_dir: &Path) -> Result<Managed> {
+    let path = manifest_path(target_dir);
+    if !path.exists() {
+        return Ok(Managed::default());
+    }
+
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| {
+        format!(
+            "Failed to parse {}\nHint: Delete the file and re-run `skillset sync` to rebuild it.",
+            path.display()
+        )
+    })
+}
+
+/// Write the manifest, dropping entries whose skill is gone from the target. An empty
+/// manifest removes the file.
+pub fn save(target_dir: &Path, managed: &mut Managed) -> Result<()> {
+    managed
+        .skills
+        .retain(|name, _| fs::symlink_metadata(target_dir.join(name)).is_ok());
+    let path = manifest_path(target_dir);
+    if managed.skills.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+
+    let mut content =
+        serde_json::to_string_pretty(managed).context("Failed to serialize managed manifest")?;
+    content.push('\n');
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Record that `name` was synced into `target_dir` from `source_dir`.
+pub fn record(
+    target_dir: &Path,
+    name: &str,
+    source_dir: &Path,
+    method: SyncMethod,
+    hash: &str,
+) -> Result<()> {
+    let mut managed = load(target_dir)?;
+    managed.skills.insert(
+        name.to_string(),
+        ManagedSkill {
+            source: canonical(source_dir).to_string_lossy().into_owned(),
+            method,
+            hash: hash.to_string(),
+            synced_at: now_timestamp(),
+            kept: None,
+        },
+    );
+    save(target_dir, &mut managed)
+}
+
+/// Record that the user kept the edited copy of `name` in `target_dir` over the source.
+pub fn keep(target_dir: &Path, name: &str, kept: Kept) -> Result<()> {
+    let mut managed = load(target_dir)?;
+    if let Some(entry) = managed.skills.get_mut(name) {
+        entry.kept = Some(kept);
+        save(target_dir, &mut managed)?;
+    }
+    Ok(())
+}
+
+/// Drop `name` from the manifest after it was removed from `target_dir`.
+pub fn forget(target_dir: &Path, name: &str) -> Result<()> {
+    let mut managed = load(target_dir)?;
+    if managed.skills.remove(name).is_some() {
+        save(target_dir, &mut managed)?;
+    }
+    Ok(())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// True if `link` is a symlink to `source_skill`, which may no longer exist.
+fn links_to(link: &Path, source_skill: &Path) -> bool {
+    let Ok(target) = fs::read_link(link) else {
+        return false;
+    };
+    let target = match link.parent() {
+        Some(parent) if target.is_relative() => parent.join(target),
+        _ => target,
+    };
+    if let (Ok(target), Ok(source)) = (target.canonicalize(), source_skill.canonicalize()) {
+        return target == source;
+    }
+    target.file_name() == source_skill.file_name()
+        && target.parent().map(canonical) == source_skill.parent().map(canonical)
+}
+
+fn skill_md_matches(source_skill: &Path, target: &Path) -> bool {
+    match (
+        fs::read(source_skill.join("SKILL.md")),
+        fs::read(target.join("SKILL.md")),
+    ) {
+        (Ok(source), Ok(target)) => source == target,
+        _ => false,
+    }
+}
+
+/// Classify the existing skill `target_dir/name` against `source_dir/name`. The target's
+/// manifest decides when it lists the skill; for targets synced before the manifest existed,
+/// a symlink to the source skill or an identical SKILL.md counts as managed.
+pub fn ownership(target_dir: &Path, name: &str, source_dir: &Path) -> Ownership {
+    let target = target_dir.join(name);
+    let source_skill = source_dir.join(name);
+    let is_symlink = fs::symlink_metadata(&target)
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false);
+    let entry = load(target_dir)
+        .ok()
+        .and_then(|mut managed| managed.skills.remove(name));
+
+    let Some(entry) = entry else {
+        return if links_to(&target, &source_skill) || skill_md_matches(&source_skill, &target) {
+            Ownership::Managed
+        } else {
+            Ownership::Foreign
+        };
+    };
+    if canonical(Path::new(&entry.source)) != canonical(source_dir) {
+        return Ownership::Foreign;
+    }
+    let unchanged = match entry.method {
+        SyncMethod::Symlink => links_to(&target, &source_skill),
+        SyncMethod::Copy => !is_symlink && tree_hash(&target).is_ok_and(|hash| hash == entry.hash),
+    };
+    if unchanged {
+        Ownership::Managed
+    } else {
+        Ownership::Modified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::{copy_skill, symlink_skill};
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_managed_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn ownership_follows_the_target_manifest() {
+        let tmp = unique_tmp("ownership");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join("source");
+        let other_source = tmp.join("other");
+        let target = tmp.join("target");
+        for dir in [source.join("pdf"), source.join("xlsx"), source.join("docx")] {
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), "# Skill").unwrap();
+        }
+        fs::create_dir_all(other_source.join("docx")).unwrap();
+        fs::write(other_source.join("docx").join("SKILL.md"), "# Skill").unwrap();
+
+        copy_skill(&source.join("pdf"), &target.join("pdf")).unwrap();
+        let hash = tree_hash(&source.join("pdf")).unwrap();
+        record(&target, "pdf", &source, SyncMethod::Copy, &hash).unwrap();
+        symlink_skill(&source.join("xlsx"), &target.join("xlsx")).unwrap();
+        record(&target, "xlsx", &source, SyncMethod::Symlink, &hash).unwrap();
+        copy_skill(&other_source.join("docx"), &target.join("docx")).unwrap();
+        record(&target, "docx", &other_source, SyncMethod::Copy, &hash).unwrap();
+
+        let synced = (
+            ownership(&target, "pdf", &source),
+            ownership(&target, "xlsx", &source),
+            ownership(&target, "docx", &source),
+        );
+        fs::write(target.join("pdf").join("notes.md"), "local edit").unwrap();
+        let edited = ownership(&target, "pdf", &source);
+        fs::remove_dir_all(target.join("docx")).unwrap();
+        forget(&target, "xlsx").unwrap();
+        let listed: Vec<String> = load(&target).unwrap().skills.into_keys().collect();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(
+            synced,
+            (Ownership::Managed, Ownership::Managed, Ownership::Foreign)
+        );
+        assert_eq!(edited, Ownership::Modified);
+        assert_eq!(listed, vec!["pdf".to_string()]);
+    }
+}
@@ -0,0 +1,241 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_NAME: &str = "skillset.json";
+
+/// A package the project depends on.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PackageEntry {
+    /// Package spec as accepted by `skillset install`
+    pub package: String,
+    /// Branch, tag, or commit to install (default branch when absent)
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Skill names or glob patterns to install from the package (empty means all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    /// Skill names or glob patterns to leave out
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Install the single selected skill under this name
+    #[serde(default, rename = "as", skip_serializing_if = "Option::is_none")]
+    pub install_as: Option<String>,
+    /// Prefix prepended to every installed skill name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+}
+
+/// Manifest lists the skill packages a project (or user store) installs from.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Manifest {
+    #[serde(default)]
+    pub packages: Vec<PackageEntry>,
+}
+
+/// Manifest path for a source directory: `.skillset/skills` -> `.skillset/skillset.json`.
+pub fn manifest_path(source_dir: &Path) -> PathBuf {
+    source_dir
+        .parent()
+        .unwrap_or(source_dir)
+        .join(MANIFEST_NAME)
+}
+
+pub fn load(source_dir: &Path) -> Result<Manifest> {
+    let path = manifest_path(source_dir);
+    if !path.exists() {
+        return Ok(Manifest::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse manifest: {}", path.display()))
+}
+
+pub fn save(source_dir: &Path, manifest: &Manifest) -> Result<()> {
+    let path = manifest_path(source_dir);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create manifest directory")?;
+    }
+    let mut content =
+        serde_json::to_string_pretty(manifest).context("Failed to serialize manifest")?;
+    content.push('\n');
+    fs::write(&path, content).context("Failed to write manifest")?;
+    Ok(())
+}
+
+impl Manifest {
+    /// Add a package, merging skill filters with an existing entry for the same package.
+    /// A package installed under different `as` names keeps one entry per name.
+    /// Returns true if the manifest changed.
+    pub fn add(&mut self, entry: PackageEntry) -> bool {
+        let Some(existing) = self
+            .packages
+            .iter_mut()
+            .find(|p| p.package == entry.package && p.install_as == entry.install_as)
+        else {
+            self.packages.push(entry);
+            return true;
+        };
+
+        let mut changed = false;
+        if existing.git_ref != entry.git_ref {
+            existing.git_ref = entry.git_ref;
+            changed = true;
+        }
+        // Re-adding without --exclude keeps the saved exclusions.
+        if !entry.exclude.is_empty() && existing.exclude != entry.exclude {
+            existing.exclude = entry.exclude;
+            changed = true;
+        }
+        // Likewise without --prefix, so a restore keeps the namespaced names.
+        if entry.prefix.is_some() && existing.prefix != entry.prefix {
+            existing.prefix = entry.prefix;
+            changed = true;
+        }
+
+        // An empty filter means "all skills", which already covers any specific skill.
+        if existing.skills.is_empty() {
+            return changed;
+        }
+        if entry.skills.is_empty() {
+            existing.skills.clear();
+            return true;
+        }
+
+        for skill in entry.skills {
+            if !existing.skills.contains(&skill) {
+                existing.skills.push(skill);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Drop the saved exclusions of a package (`install <package> --exclude=`).
+    /// Returns true if the manifest changed.
+    pub fn clear_exclude(&mut self, package: &str, install_as: Option<&str>) -> bool {
+        let Some(existing) = self
+            .packages
+            .iter_mut()
+            .find(|p| p.package == package && p.install_as.as_deref() == install_as)
+        else {
+            return false;
+        };
+        let changed = !existing.exclude.is_empty();
+        existing.exclude.clear();
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(package: &str, skills: &[&str]) -> PackageEntry {
+        PackageEntry {
+            package: package.to_string(),
+            git_ref: None,
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn add_merges_skill_filters_for_same_package() {
+        let mut manifest = Manifest::default();
+        assert!(manifest.add(entry("a/b", &["one"])));
+        assert!(manifest.add(entry("a/b", &["two"])));
+        assert!(!manifest.add(entry("a/b", &["one"])));
+
+        assert_eq!(manifest.packages, vec![entry("a/b", &["one", "two"])]);
+    }
+
+    #[test]
+    fn add_without_filter_widens_to_all_skills() {
+        let mut manifest = Manifest::default();
+        manifest.add(entry("a/b", &["one"]));
+        assert!(manifest.add(entry("a/b", &[])));
+        assert!(!manifest.add(entry("a/b", &["two"])));
+
+        assert_eq!(manifest.packages, vec![entry("a/b", &[])]);
+    }
+
+    #[test]
+    fn add_without_exclude_keeps_saved_exclusions() {
+        let mut manifest = Manifest::default();
+        let mut excluding = entry("a/b", &[]);
+        excluding.exclude = vec!["beta-*".to_string()];
+        manifest.add(excluding.clone());
+
+        assert!(!manifest.add(entry("a/b", &["pdf"])));
+        assert_eq!(manifest.packages, vec![excluding.clone()]);
+
+        let mut replaced = entry("a/b", &[]);
+        replaced.exclude = vec!["alpha-*".to_string()];
+        assert!(manifest.add(replaced.clone()));
+        assert_eq!(manifest.packages, vec![replaced]);
+
+        assert!(manifest.clear_exclude("a/b", None));
+        assert!(!manifest.clear_exclude("a/b", None));
+        assert!(!manifest.clear_exclude("c/d", None));
+        assert_eq!(manifest.packages, vec![entry("a/b", &[])]);
+    }
+
+    #[test]
+    fn add_without_prefix_keeps_saved_prefix() {
+        let mut manifest = Manifest::default();
+        let mut prefixed = entry("a/b", &["one"]);
+        prefixed.prefix = Some("acme-".to_string());
+        manifest.add(prefixed);
+
+        assert!(manifest.add(entry("a/b", &["two"])));
+        assert_eq!(manifest.packages[0].prefix.as_deref(), Some("acme-"));
+        assert_eq!(manifest.packages[0].skills, vec!["one", "two"]);
+    }
+
+    #[test]
+    fn manifest_round_trips_through_disk() {
+        let tmp =
+            std::env::temp_dir().join(format!("skillset_manifest_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join(".skillset").join("skills");
+        let mut manifest = Manifest::default();
+        manifest.add(entry("a/b", &["one"]));
+
+        save(&source, &manifest).unwrap();
+        let loaded = load(&source).unwrap();
+
+        let path_exists = tmp.join(".skillset").join("skillset.json").exists();
+        fs::remove_dir_all(&tmp).ok();
+        assert!(path_exists);
+        assert_eq!(loaded.packages, manifest.packages);
+    }
+
+    #[test]
+    fn add_replaces_ref_for_same_package() {
+        let mut manifest = Manifest::default();
+        manifest.add(entry("a/b", &[]));
+        let mut pinned = entry("a/b", &[]);
+        pinned.git_ref = Some("v1.2.0".to_string());
+
+        assert!(manifest.add(pinned.clone()));
+        assert!(!manifest.add(pinned.clone()));
+        assert_eq!(manifest.packages, vec![pinned]);
+    }
+
+    #[test]
+    fn add_keeps_separate_entries_per_install_name() {
+        let mut manifest = Manifest::default();
+        let mut first = entry("a/b", &["review"]);
+        first.install_as = Some("a-review".to_string());
+        let mut second = entry("a/b", &["lint"]);
+        second.install_as = Some("a-lint".to_string());
+
+        assert!(manifest.add(first.clone()));
+        assert!(manifest.add(second.clone()));
+        assert_eq!(manifest.packages, vec![first, second]);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::hash::file_hashes;
+use crate::skills::{skill_target_unchanged, SyncMethod};
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How a skill from a package compares with the copy already in the store.
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    New,
+    Identical,
+    Changed {
+        modified: Vec<String>,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+}
+
+/// Compare an incoming skill directory with the installed one, file by file.
+pub fn compare(incoming: &Path, installed: &Path) -> Result<Change> {
+    if !installed.is_dir() {
+        return Ok(Change::New);
+    }
+    let new_files = file_hashes(incoming)?;
+    let old_files = file_hashes(installed)?;
+    if new_files == old_files {
+        return Ok(Change::Identical);
+    }
+    let mut modified = Vec::new();
+    let mut added = Vec::new();
+    for (path, hash) in &new_files {
+        match old_files.get(path) {
+            None => added.push(path.clone()),
+            Some(old) if old != hash => modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    let removed = old_files
+        .keys()
+        .filter(|path| !new_files.contains_key(*path))
+        .cloned()
+        .collect();
+    Ok(Change::Changed {
+        modified,
+        added,
+        removed,
+    })
+}
+
+/// Print a unified diff of one file in a skill (`old` copy → `new` copy), with headers
+/// `<label>/<name>/<file>` for the (old, new) labels.
+pub fn print_file_diff(old: &Path, new: &Path, labels: (&str, &str), name: &str, file: &str) {
+    let (Ok(old), Ok(new)) = (
+        fs::read_to_string(old.join(file)),
+        fs::read_to_string(new.join(file)),
+    ) else {
+        println!("        (binary file differs)");
+        return;
+    };
+    if old == new {
+        println!("        (only the executable bit changed)");
+        return;
+    }
+    let diff = similar::TextDiff::from_lines(&old, &new);
+    let unified = diff
+        .unified_diff()
+        .context_radius(3)
+        .header(
+            &format!("{}/{}/{}", labels.0, name, file),
+            &format!("{}/{}/{}", labels.1, name, file),
+        )
+        .to_string();
+    for line in unified.lines() {
+        println!("        {}", line);
+    }
+}
+
+/// Print what installing (name, directory) pairs into `dest` would change. Without `force`,
+/// changed skills are only overwritten after a prompt.
+pub fn print_install(
+    skills: &[(&str, &Path)],
+    dest: &Path,
+    dest_label: &str,
+    force: bool,
+) -> Result<()> {
+    println!(
+        "[DRY RUN] {} skill(s) into {} ({}):",
+        skills.len(),
+        dest_label,
+        dest.display()
+    );
+    for (name, incoming) in skills {
+        let installed = dest.join(name);
+        match compare(incoming, &installed)? {
+            Change::New => println!("  + {}  new", name),
+            Change::Identical => println!("  = {}  identical", name),
+            Change::Changed {
+                modified,
+                added,
+                removed,
+            } => {
+                let ask = if force {
+                    ""
+                } else {
+                    "; would ask before overwriting"
+                };
+                println!(
+                    "  ~ {}  changed ({} modified, {} added, {} removed){}",
+                    name,
+                    modified.len(),
+                    added.len(),
+                    removed.len(),
+                    ask
+                );
+                for file in &modified {
+                    println!("      modified: {}", file);
+                    print_file_diff(&installed, incoming, ("installed", "package"), name, file);
+                }
+                for file in &added {
+                    println!("      added:    {}", file);
+                }
+                for file in &removed {
+                    println!("      removed:  {}", file);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// What `--sync` would do with one skill in one target after the install.
+fn sync_action(
+    incoming: &Path,
+    installed: &Path,
+    target: &Path,
+    method: SyncMethod,
+    force: bool,
+) -> String {
+    if fs::symlink_metadata(target).is_err() {
+        return format!("would {}", method.action());
+    }
+    // Copies are compared with the package's version; symlinks with the store path.
+    let current = match method {
+        SyncMethod::Copy => skill_target_unchanged(incoming, target, method),
+        SyncMethod::Symlink => skill_target_unchanged(installed, target, method),
+    };
+    if current {
+        return "up to date".to_string();
+    }
+    let outcome = match method {
+        SyncMethod::Copy => "overwritten",
+        SyncMethod::Symlink => "replaced with a symlink",
+    };
+    if force {
+        format!("exists; would be {}", outcome)
+    } else {
+        format!("exists and differs; would ask before it is {}", outcome)
+    }
+}
+
+/// Print what `--sync` would do in each target for the skills being installed into `source`.
+pub fn print_sync(
+    skills: &[(&str, &Path)],
+    source: &Path,
+    targets: &[(String, PathBuf)],
+    method: SyncMethod,
+    force: bool,
+) {
+    println!(
+        "[DRY RUN] --sync would {} into {} target(s):",
+        method.action(),
+        targets.len()
+    );
+    for (label, path) in targets {
+        println!("  {} ({})", label, path.display());
+        for (name, incoming) in skills {
+            let action = sync_action(
+                incoming,
+                &source.join(name),
+                &path.join(name),
+                method,
+                force,
+            );
+            println!("    {}: {}", name, action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_preview_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn compare_classifies_new_identical_and_changed_skills() {
+        let tmp = unique_tmp("compare");
+        let _ = fs::remove_dir_all(&tmp);
+        let incoming = tmp.join("package").join("pdf");
+        let installed = tmp.join("store").join("pdf");
+        fs::create_dir_all(incoming.join("scripts")).unwrap();
+        fs::write(incoming.join("SKILL.md"), "# v2").unwrap();
+        fs::write(incoming.join("scripts").join("run.sh"), "echo hi").unwrap();
+
+        let new = compare(&incoming, &installed).unwrap();
+        fs::create_dir_all(&installed).unwrap();
+        fs::write(installed.join("SKILL.md"), "# v1").unwrap();
+        fs::write(installed.join("old.md"), "old").unwrap();
+        let changed = compare(&incoming, &installed).unwrap();
+        let identical = compare(&incoming, &incoming).unwrap();
+        let copy_target = tmp.join("target").join("pdf");
+        crate::skills::copy_skill(&incoming, &copy_target).unwrap();
+        let up_to_date = sync_action(&incoming, &installed, &copy_target, SyncMethod::Copy, false);
+        let missing = sync_action(
+            &incoming,
+            &installed,
+            &tmp.join("target").join("xlsx"),
+            SyncMethod::Symlink,
+            false,
+        );
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(new, Change::New);
+        assert_eq!(identical, Change::Identical);
+        assert_eq!(
+            changed,
+            Change::Changed {
+                modified: vec!["SKILL.md".to_string()],
+                added: vec!["scripts/run.sh".to_string()],
+                removed: vec!["old.md".to_string()],
+            }
+        );
+        assert_eq!(up_to_date, "up to date");
+        assert_eq!(missing, "would symlink");
+    }
+}
@@ -0,0 +1,454 @@
+use crate::cache;
+use crate::hash::{file_hashes, tree_hash};
+use crate::install::confirm;
+use crate::managed::{self, Ownership};
+use crate::preview::{compare, print_file_diff, Change};
+use crate::skills::{copy_skill, show_diff, SyncMethod};
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Snapshots of copied skills as sync last wrote them, keyed by tree hash. They are the base
+/// for three-way merges when a target copy was edited in place.
+fn snapshots_dir() -> Result<PathBuf> {
+    Ok(cache::cache_root()?.join("snapshots"))
+}
+
+/// Keep a snapshot of `skill` (whose tree hash is `hash`) unless one already exists.
+pub fn save_base(skill: &Path, hash: &str) -> Result<()> {
+    let dir = snapshots_dir()?.join(hash);
+    if dir.is_dir() {
+        return Ok(());
+    }
+    let partial = dir.with_extension("partial");
+    copy_skill(skill, &partial)?;
+    fs::rename(&partial, &dir).context("Failed to store skill snapshot")
+}
+
+/// A managed copy that was edited in its target since the last sync.
+pub struct Divergence {
+    /// Snapshot of what sync last wrote, if it is still cached
+    pub base: Option<PathBuf>,
+    /// Whether the source changed since the last sync too
+    pub source_changed: bool,
+    /// Whether the user already chose to keep this exact target copy over this source
+    pub kept: bool,
+}
+
+/// Check whether the copied skill `target_dir/name` diverged from what sync last wrote there,
+/// using the hash in the target's ownership manifest.
+pub fn divergence(target_dir: &Path, name: &str, source_dir: &Path) -> Option<Divergence> {
+    let entry = managed::load(target_dir).ok()?.skills.remove(name)?;
+    if entry.method != SyncMethod::Copy
+        || managed::ownership(target_dir, name, source_dir) != Ownership::Modified
+    {
+        return None;
+    }
+    let base = snapshots_dir()
+        .ok()
+        .map(|dir| dir.join(&entry.hash))
+        .filter(|dir| dir.is_dir());
+    let source_hash = tree_hash(&source_dir.join(name)).ok();
+    let source_changed = source_hash.as_ref() != Some(&entry.hash);
+    let kept = entry.kept.is_some_and(|kept| {
+        source_hash.as_ref() == Some(&kept.source)
+            && tree_hash(&target_dir.join(name)).is_ok_and(|hash| hash == kept.target)
+    });
+    Some(Divergence {
+        base,
+        source_changed,
+        kept,
+    })
+}
+
+/// How to reconcile a diverged target copy with the source.
+#[derive(Debug, PartialEq)]
+pub enum Resolution {
+    KeepTarget,
+    TakeSource,
+    Merge,
+}
+
+fn changed_files(new: &Path, old: &Path) -> String {
+    match compare(new, old) {
+        Ok(Change::Changed {
+            modified,
+            added,
+            removed,
+        }) => modified
+            .into_iter()
+            .chain(added)
+            .chain(removed)
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => "(nothing)".to_string(),
+    }
+}
+
+/// Explain how `target` diverged and ask whether to keep it, take the source, or merge.
+pub fn ask(
+    name: &str,
+    label: &str,
+    source: &Path,
+    target: &Path,
+    divergence: &Divergence,
+) -> Result<Resolution> {
+    println!(
+        "  Skill '{}' at {} was edited there since the last sync.",
+        name, label
+    );
+    match &divergence.base {
+        Some(base) => {
+            println!("    Edited in {}: {}", label, changed_files(target, base));
+            if divergence.source_changed {
+                println!("    Changed in source: {}", changed_files(source, base));
+            } else {
+                println!("    The source is unchanged since the last sync.");
+            }
+        }
+        None => println!("    No snapshot of the last sync is cached, so merging is unavailable."),
+    }
+
+    let merge = if divergence.base.is_some() {
+        ", [m]erge"
+    } else {
+        ""
+    };
+    loop {
+        print!("  [k]eep target, [t]ake source{}, [d]iff? [k] ", merge);
+        std::io::stdout().flush().context("Flush stdout")?;
+        let mut input = String::new();
+        std::io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+        match input.trim().to_lowercase().as_str() {
+            "" | "k" | "keep" => return Ok(Resolution::KeepTarget),
+            "t" | "take" => return Ok(Resolution::TakeSource),
+            "m" | "merge" if divergence.base.is_some() => return Ok(Resolution::Merge),
+            "d" | "diff" => show_diff(source, target, name, label),
+            _ => {}
+        }
+    }
+}
+
+/// Merge one text file changed on both sides with `git merge-file`, writing the result to
+/// `out`. Returns false if it conflicted (the result then has conflict markers).
+fn merge_file(base: &Path, target: &Path, source: &Path, out: &Path, label: &str) -> Result<bool> {
+    let empty = std::env::temp_dir().join(format!("skillset-merge-base-{}", std::process::id()));
+    let base = if base.exists() {
+        base.to_path_buf()
+    } else {
+        fs::write(&empty, "").context("Failed to create empty merge base")?;
+        empty.clone()
+    };
+    let output = Command::new("git")
+        .args([
+            "merge-file",
+            "-p",
+            "-L",
+            label,
+            "-L",
+            "last sync",
+            "-L",
+            "source",
+        ])
+        .arg(target)
+        .arg(&base)
+        .arg(source)
+        .output()
+        .context("Failed to run git merge-file. Is git installed?")?;
+    let _ = fs::remove_file(&empty);
+    match output.status.code() {
+        Some(code) if (0..128).contains(&code) => {
+            fs::write(out, &output.stdout)
+                .with_context(|| format!("Failed to write {}", out.display()))?;
+            Ok(code == 0)
+        }
+        _ => anyhow::bail!(
+            "git merge-file failed for {}: {}",
+            source.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+}
+
+/// Three-way merge of a skill: apply the edits made in `target` since `base` to a copy of
+/// `source` written to `out`, leaving both inputs untouched. Text files changed on both sides
+/// are merged line by line. Returns the files that conflicted: text conflicts are left with
+/// conflict markers, while binary files and edit/delete conflicts keep the target version.
+pub fn merge(
+    base: &Path,
+    target: &Path,
+    source: &Path,
+    out: &Path,
+    label: &str,
+) -> Result<Vec<String>> {
+    copy_skill(source, out)?;
+    let base_files = file_hashes(base)?;
+    let target_files = file_hashes(target)?;
+    let source_files = file_hashes(source)?;
+    let files: BTreeSet<&String> = base_files
+        .keys()
+        .chain(target_files.keys())
+        .chain(source_files.keys())
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for file in files {
+        let (old, ours, theirs) = (
+            base_files.get(file),
+            target_files.get(file),
+            source_files.get(file),
+        );
+        // Unchanged in the target, or changed the same way on both sides: the source is right.
+        if ours == old || ours == theirs {
+            continue;
+        }
+        let out_file = out.join(file);
+        let text = |dir: &Path| fs::read_to_string(dir.join(file)).is_ok();
+        let mergeable = ours.is_some() && theirs.is_some() && text(target) && text(source);
+        if theirs != old && mergeable {
+            let (base_file, target_file) = (base.join(file), target.join(file));
+            if !merge_file(
+                &base_file,
+                &target_file,
+                &source.join(file),
+                &out_file,
+                label,
+            )? {
+                conflicts.push(file.clone());
+            }
+            continue;
+        }
+        // Only the target changed it, or it can't be merged: take the target's version.
+        if theirs != old {
+            conflicts.push(file.clone());
+        }
+        match ours {
+            Some(_) => {
+                if let Some(parent) = out_file.parent() {
+                    fs::create_dir_all(parent).context("Failed to create directory")?;
+                }
+                fs::copy(target.join(file), &out_file)
+                    .with_context(|| format!("Failed to copy {}", file))?;
+            }
+            None => fs::remove_file(&out_file)
+                .with_context(|| format!("Failed to remove {}", out_file.display()))?,
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Copy a skill edited inside a target (`target_dir/name`) back into the source, so the
+/// source of truth picks up the edits. The target is then recorded as in sync.
+pub fn pull(
+    name: &str,
+    source_dir: &Path,
+    (label, target_dir): (&str, &Path),
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let target = target_dir.join(name);
+    let source = source_dir.join(name);
+    let metadata = fs::symlink_metadata(&target).map_err(|_| {
+        anyhow::anyhow!(
+            "Skill '{}' not found in {} ({})\nHint: Run `skillset list --tool \"{}\"` to see its skills.",
+            name,
+            label,
+            target_dir.display(),
+            label
+        )
+    })?;
+    if !source.join("SKILL.md").exists() {
+        anyhow::bail!(
+            "Skill '{}' is not in the source: {}\nHint: Run `skillset adopt {} --from \"{}\"` to import it.",
+            name,
+            source_dir.display(),
+            name,
+            label
+        );
+    }
+    if metadata.file_type().is_symlink() {
+        println!(
+            "{} at {} is a symlink, so edits made there are already in the source.",
+            name, label
+        );
+        return Ok(());
+    }
+
+    let Change::Changed {
+        modified,
+        added,
+        removed,
+    } = compare(&target, &source)?
+    else {
+        println!("{} at {} already matches the source.", name, label);
+        return Ok(());
+    };
+    let prefix = if dry_run { "[DRY RUN] " } else { "" };
+    println!(
+        "{}Pulling {} from {} ({}) into {}:",
+        prefix,
+        name,
+        label,
+        target.display(),
+        source_dir.display()
+    );
+    for file in &modified {
+        println!("  modified: {}", file);
+        print_file_diff(&source, &target, ("source", label), name, file);
+    }
+    for file in &added {
+        println!("  added:    {}", file);
+    }
+    for file in &removed {
+        println!("  removed:  {}", file);
+    }
+
+    if divergence(target_dir, name, source_dir).is_some_and(|d| d.source_changed) {
+        println!(
+            "Warning: the source also changed since {} was last synced, and pulling replaces those changes.\nHint: Run `skillset sync --copy` and choose merge to combine both.",
+            label
+        );
+    }
+
+    if dry_run {
+        println!("[DRY RUN] No changes made.");
+        return Ok(());
+    }
+    if !force && !confirm(&format!("Overwrite '{}' in the source?", name))? {
+        println!("Pull cancelled.");
+        return Ok(());
+    }
+
+    copy_skill(&target, &source)?;
+    let hash = tree_hash(&source)?;
+    managed::record(target_dir, name, source_dir, SyncMethod::Copy, &hash)?;
+    if let Err(e) = save_base(&source, &hash) {
+        eprintln!("Warning: failed to snapshot '{}': {}", name, e);
+    }
+    println!("Pulled {} from {} into the source.", name, label);
+    println!("Run `skillset sync` to update the other targets.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_reconcile_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn write(dir: &Path, file: &str, contents: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn merge_applies_target_edits_to_the_source() {
+        let tmp = unique_tmp("merge");
+        let _ = fs::remove_dir_all(&tmp);
+        let (base, target, source) = (tmp.join("base"), tmp.join("target"), tmp.join("source"));
+        for dir in [&base, &target, &source] {
+            write(dir, "SKILL.md", "# Skill\n\nintro\n\nsteps\n\nfooter\n");
+            write(dir, "notes.md", "one\n");
+            write(dir, "scripts/run.sh", "echo hi\n");
+            write(dir, "old.md", "old\n");
+            write(dir, "gone.md", "gone\n");
+        }
+        // Both sides edit different lines of SKILL.md; only the target touches the rest.
+        write(
+            &target,
+            "SKILL.md",
+            "# Skill\n\nintro (edited)\n\nsteps\n\nfooter\n",
+        );
+        write(
+            &source,
+            "SKILL.md",
+            "# Skill\n\nintro\n\nsteps\n\nfooter (new)\n",
+        );
+        write(&target, "scripts/run.sh", "echo hello\n");
+        write(&target, "extra.md", "extra\n");
+        fs::remove_file(target.join("old.md")).unwrap();
+        // Both sides edit the same line of notes.md.
+        write(&target, "notes.md", "target\n");
+        write(&source, "notes.md", "source\n");
+        // The target edits a file the source deleted.
+        write(&target, "gone.md", "kept\n");
+        fs::remove_file(source.join("gone.md")).unwrap();
+
+        let out = tmp.join("out");
+        let conflicts = merge(&base, &target, &source, &out, "Cursor").unwrap();
+        let read = |file: &str| fs::read_to_string(out.join(file)).unwrap_or_default();
+        let merged = (
+            read("SKILL.md"),
+            read("scripts/run.sh"),
+            read("extra.md"),
+            out.join("old.md").exists(),
+            read("notes.md"),
+        );
+        let gone = read("gone.md");
+        let untouched = (
+            fs::read_to_string(source.join("notes.md")).unwrap(),
+            fs::read_to_string(target.join("notes.md")).unwrap(),
+        );
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(
+            conflicts,
+            vec!["gone.md".to_string(), "notes.md".to_string()]
+        );
+        assert_eq!(gone, "kept\n");
+        assert_eq!(untouched, ("source\n".to_string(), "target\n".to_string()));
+        assert_eq!(
+            merged.0,
+            "# Skill\n\nintro (edited)\n\nsteps\n\nfooter (new)\n"
+        );
+        assert_eq!(merged.1, "echo hello\n");
+        assert_eq!(merged.2, "extra\n");
+        assert!(!merged.3);
+        assert!(merged.4.contains("<<<<<<< Cursor") && merged.4.contains(">>>>>>> source"));
+    }
+
+    #[test]
+    fn kept_targets_stay_kept_until_either_side_changes() {
+        let tmp = unique_tmp("kept");
+        let _ = fs::remove_dir_all(&tmp);
+        let (source, target) = (tmp.join("source"), tmp.join("target"));
+        write(&source.join("review"), "SKILL.md", "# review\n");
+        copy_skill(&source.join("review"), &target.join("review")).unwrap();
+        let synced = tree_hash(&source.join("review")).unwrap();
+        managed::record(&target, "review", &source, SyncMethod::Copy, &synced).unwrap();
+        write(&target.join("review"), "SKILL.md", "# review (edited)\n");
+        let kept = || divergence(&target, "review", &source).map(|d| d.kept);
+
+        let before = kept();
+        managed::keep(
+            &target,
+            "review",
+            managed::Kept {
+                target: tree_hash(&target.join("review")).unwrap(),
+                source: synced,
+            },
+        )
+        .unwrap();
+        let after = kept();
+        write(&source.join("review"), "SKILL.md", "# review v2\n");
+        let source_changed = kept();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(
+            (before, after, source_changed),
+            (Some(false), Some(true), Some(false))
+        );
+    }
+}
@@ -23,7 +23,8 @@ fn registry_path() -> Result<PathBuf> {
     Ok(dirs.config_dir().join("registry.json"))
 }
 
-fn now_timestamp() -> String {
+/// Date stamp (`YYYY-MM-DD`) for sync records.
+pub fn now_timestamp() -> String {
     let duration = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default();
@@ -56,12 +57,13 @@ pub fn load() -> Result<Registry> {
         }
     };
 
-    // Clean stale entries (paths that no longer exist)
+    // Clean stale entries (paths that no longer exist). Dangling symlinks are kept so
+    // `sync --prune` can still find and remove them.
     let mut changed = false;
     for instances in registry.skills.values_mut() {
         let before = instances.len();
         instances.retain(|inst| {
-            let exists = PathBuf::from(&inst.path).exists();
+            let exists = fs::symlink_metadata(&inst.path).is_ok();
             if !exists {
                 changed = true;
             }
@@ -1,9 +1,18 @@
+use crate::deps;
+use crate::hash::tree_hash;
+use crate::install::{confirm, split_ref};
+use crate::lockfile;
+use crate::managed::{self, Ownership};
+use crate::manifest::{self, PackageEntry};
 use crate::registry;
 use anyhow::{Context, Result};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+/// (target label, path of the skill inside that target)
+type TargetSkill = (String, PathBuf);
+
 /// Remove a skill from all configured targets.
 /// Only removes target instances that match the active source skill, then removes it from source.
 pub fn remove_skill(
@@ -27,34 +36,30 @@ pub fn remove_skill(
         return Ok(());
     }
 
-    // Collect targets where the skill exists and appears to be managed by this source.
-    let mut targets_with_skill = Vec::new();
-    let mut skipped_targets = Vec::new();
-    for (label, target_path) in targets {
-        let skill_path = target_path.join(name);
-        if !skill_path.exists() {
-            continue;
-        }
-
-        if target_matches_source(&source_skill_path, &skill_path) {
-            targets_with_skill.push((label.clone(), skill_path));
-        } else {
-            skipped_targets.push((label.clone(), skill_path));
+    let dependents = deps::dependents(source_dir, name)?;
+    if !dependents.is_empty() {
+        if !yes {
+            anyhow::bail!(
+                "'{}' is required by {}\nHint: Remove those skills first, or pass --yes to remove it anyway.",
+                name,
+                dependents.join(", ")
+            );
         }
+        eprintln!(
+            "Warning: '{}' is still required by {}.",
+            name,
+            dependents.join(", ")
+        );
     }
 
+    let (targets_with_skill, skipped_targets) = matching_targets(name, targets, &source_skill_path);
+
     if targets_with_skill.is_empty() {
         println!(
             "Skill '{}' was found in source but not in any matching configured target.",
             name
         );
-        for (label, skill_path) in &skipped_targets {
-            println!(
-                "  Skipped {} at {} (does not match source)",
-                label,
-                skill_path.display()
-            );
-        }
+        print_skipped(&skipped_targets);
         remove_from_source(name, &source_skill_path)?;
         cleanup_registry(name);
         println!("Remove complete.");
@@ -85,28 +90,243 @@ pub fn remove_skill(
         }
     }
 
-    // Remove from targets
-    for (label, skill_path) in &targets_with_skill {
+    remove_from_targets(name, &targets_with_skill)?;
+    print_skipped(&skipped_targets);
+
+    remove_from_source(name, &source_skill_path)?;
+    cleanup_registry(name);
+
+    println!("Remove complete.");
+    Ok(())
+}
+
+/// Targets where the skill exists, split into those managed by this source (see
+/// `target_matches_source`) and those that only share the name or were changed in place.
+fn matching_targets(
+    name: &str,
+    targets: &[(String, PathBuf)],
+    source_skill_path: &Path,
+) -> (Vec<TargetSkill>, Vec<TargetSkill>) {
+    let mut matching = Vec::new();
+    let mut skipped = Vec::new();
+    for (label, target_path) in targets {
+        let skill_path = target_path.join(name);
+        if !skill_path.exists() {
+            continue;
+        }
+
+        if target_matches_source(source_skill_path, &skill_path) {
+            matching.push((label.clone(), skill_path));
+        } else {
+            skipped.push((label.clone(), skill_path));
+        }
+    }
+    (matching, skipped)
+}
+
+fn remove_from_targets(name: &str, targets: &[TargetSkill]) -> Result<()> {
+    for (label, skill_path) in targets {
         remove_path(skill_path)
             .with_context(|| format!("Failed to remove skill '{}' from {}", name, label))?;
         if let Err(e) = registry::remove_path(name, &skill_path.to_string_lossy()) {
             eprintln!("Warning: failed to update registry for '{}': {}", name, e);
         }
+        if let Some(target_dir) = skill_path.parent() {
+            managed::forget(target_dir, name)?;
+        }
         println!("  Removed {} from {}", name, label);
     }
+    Ok(())
+}
 
-    for (label, skill_path) in &skipped_targets {
+fn print_skipped(skipped: &[TargetSkill]) {
+    for (label, skill_path) in skipped {
         println!(
             "  Skipped {} at {} (does not match source)",
             label,
             skill_path.display()
         );
     }
+}
 
-    remove_from_source(name, &source_skill_path)?;
-    cleanup_registry(name);
+/// True if an installed spec (and ref) is `package`. A package without `@ref` matches every ref.
+fn same_package(spec: &str, git_ref: Option<&str>, package: &str) -> bool {
+    let (base, wanted_ref) = split_ref(package);
+    let (spec_base, spec_ref) = split_ref(spec);
+    spec_base == base && (wanted_ref.is_none() || spec_ref.or(git_ref) == wanted_ref)
+}
 
-    println!("Remove complete.");
+/// Remove every skill a package installed (per the lockfile) from the source and the targets
+/// that match it, then drop the package from the lockfile and manifest.
+/// Skills with local changes are kept unless the user confirms; `yes` skips all prompts and
+/// keeps them, and `remove_modified` removes them without asking. Like `remove_skill`, skills
+/// outside the package that still require it block the uninstall unless `yes` is set.
+pub fn uninstall_package(
+    package: &str,
+    targets: &[(String, PathBuf)],
+    source_dir: &Path,
+    yes: bool,
+    remove_modified: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let mut lock = lockfile::load(source_dir)?;
+    let mut manifest = manifest::load(source_dir)?;
+    let names: Vec<String> = lock
+        .skills
+        .iter()
+        .filter(|(_, locked)| same_package(&locked.package, locked.git_ref.as_deref(), package))
+        .map(|(name, _)| name.clone())
+        .collect();
+    let in_manifest =
+        |entry: &PackageEntry| same_package(&entry.package, entry.git_ref.as_deref(), package);
+    let listed = manifest.packages.iter().any(in_manifest);
+    if names.is_empty() && !listed {
+        anyhow::bail!(
+            "Package '{}' is not installed in {}\nHint: Use the package spec recorded in {}.",
+            package,
+            source_dir.display(),
+            lockfile::lock_path(source_dir).display()
+        );
+    }
+
+    let mut dependents = Vec::new();
+    for name in &names {
+        for dependent in deps::dependents(source_dir, name)? {
+            if !names.contains(&dependent) {
+                dependents.push(format!("{} (requires {})", dependent, name));
+            }
+        }
+    }
+    if !dependents.is_empty() {
+        if !yes {
+            anyhow::bail!(
+                "Skills outside {} still require it: {}\nHint: Remove those skills first, or pass --yes to uninstall anyway.",
+                package,
+                dependents.join(", ")
+            );
+        }
+        eprintln!(
+            "Warning: skills outside {} still require it: {}",
+            package,
+            dependents.join(", ")
+        );
+    }
+
+    if dry_run {
+        return preview_uninstall(
+            package,
+            &names,
+            &lock,
+            targets,
+            source_dir,
+            yes,
+            remove_modified,
+        );
+    }
+
+    if !names.is_empty() && !yes {
+        let prompt = format!(
+            "Uninstall {} ({} skill(s): {})?",
+            package,
+            names.len(),
+            names.join(", ")
+        );
+        if !confirm(&prompt)? {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut kept = Vec::new();
+    for name in &names {
+        let source_skill_path = source_dir.join(name);
+        let locked = &lock.skills[name];
+        let modified = source_skill_path.is_dir() && tree_hash(&source_skill_path)? != locked.hash;
+        if modified && !remove_modified {
+            let remove =
+                !yes && confirm(&format!("'{}' has local changes. Remove it anyway?", name))?;
+            if !remove {
+                println!("  Kept {} (modified locally)", name);
+                kept.push(name.clone());
+                continue;
+            }
+        }
+
+        let (matching, skipped) = matching_targets(name, targets, &source_skill_path);
+        remove_from_targets(name, &matching)?;
+        print_skipped(&skipped);
+        remove_from_source(name, &source_skill_path)?;
+        cleanup_registry(name);
+    }
+
+    // Kept skills stay in the source as local skills, no longer tied to the package.
+    for name in &names {
+        lock.skills.remove(name);
+    }
+    lockfile::save(source_dir, &lock)?;
+    let before = manifest.packages.len();
+    manifest.packages.retain(|entry| !in_manifest(entry));
+    if manifest.packages.len() != before {
+        manifest::save(source_dir, &manifest)?;
+        println!(
+            "Removed {} from {}",
+            package,
+            manifest::manifest_path(source_dir).display()
+        );
+    }
+
+    println!(
+        "Uninstalled {}: removed {} skill(s).",
+        package,
+        names.len() - kept.len()
+    );
+    if !kept.is_empty() {
+        println!(
+            "Kept with local changes (now untracked): {}",
+            kept.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Print what `uninstall_package` would remove without touching anything.
+fn preview_uninstall(
+    package: &str,
+    names: &[String],
+    lock: &lockfile::Lockfile,
+    targets: &[(String, PathBuf)],
+    source_dir: &Path,
+    yes: bool,
+    remove_modified: bool,
+) -> Result<()> {
+    println!("[DRY RUN] Uninstall {}:", package);
+    for name in names {
+        let source_skill_path = source_dir.join(name);
+        let modified =
+            source_skill_path.is_dir() && tree_hash(&source_skill_path)? != lock.skills[name].hash;
+        if modified && !remove_modified {
+            if yes {
+                println!("[DRY RUN]   Would keep {} (modified locally)", name);
+                continue;
+            }
+            println!(
+                "[DRY RUN]   {} is modified locally; would ask before removing it",
+                name
+            );
+        }
+        let (matching, skipped) = matching_targets(name, targets, &source_skill_path);
+        for (label, _) in &matching {
+            println!("[DRY RUN]   Would remove {} from {}", name, label);
+        }
+        print_skipped(&skipped);
+        if source_skill_path.exists() {
+            println!("[DRY RUN]   Would remove {} from source", name);
+        }
+    }
+    println!(
+        "[DRY RUN] Would drop {} from the lockfile and manifest. No changes made.",
+        package
+    );
     Ok(())
 }
 
@@ -134,54 +354,15 @@ fn remove_path(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// True if `target` is `source`'s skill as synced by skillset and not changed since (see
+/// `managed::ownership`).
 fn target_matches_source(source: &Path, target: &Path) -> bool {
-    if target_symlink_matches_source(source, target) {
-        return true;
-    }
-
-    skill_md_matches_source(source, target)
-}
-
-fn target_symlink_matches_source(source: &Path, target: &Path) -> bool {
-    let metadata = match fs::symlink_metadata(target) {
-        Ok(metadata) => metadata,
-        Err(_) => return false,
-    };
-    if !metadata.file_type().is_symlink() {
+    let (Some(source_dir), Some(target_dir), Some(name)) =
+        (source.parent(), target.parent(), target.file_name())
+    else {
         return false;
-    }
-
-    let link_target = match fs::read_link(target) {
-        Ok(path) => path,
-        Err(_) => return false,
     };
-    let resolved_target = if link_target.is_absolute() {
-        link_target
-    } else {
-        target
-            .parent()
-            .map(|parent| parent.join(&link_target))
-            .unwrap_or(link_target)
-    };
-
-    match (source.canonicalize(), resolved_target.canonicalize()) {
-        (Ok(source), Ok(target)) => source == target,
-        _ => false,
-    }
-}
-
-fn skill_md_matches_source(source: &Path, target: &Path) -> bool {
-    let source_md = source.join("SKILL.md");
-    let target_md = target.join("SKILL.md");
-
-    if !source_md.exists() || !target_md.exists() {
-        return false;
-    }
-
-    match (fs::read(source_md), fs::read(target_md)) {
-        (Ok(source), Ok(target)) => source == target,
-        _ => false,
-    }
+    managed::ownership(target_dir, &name.to_string_lossy(), source_dir) == Ownership::Managed
 }
 
 #[cfg(test)]
@@ -253,4 +434,146 @@ mod tests {
     fn symlink_dir(from: &Path, to: &Path) -> Result<()> {
         std::os::windows::fs::symlink_dir(from, to).context("Failed to create symlink")
     }
+
+    #[test]
+    fn remove_refuses_skill_that_others_require() {
+        let tmp = unique_tmp("dependents");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join("source");
+        for (name, requires) in [("deploy", "[k8s-conventions]"), ("k8s-conventions", "[]")] {
+            fs::create_dir_all(source.join(name)).unwrap();
+            fs::write(
+                source.join(name).join("SKILL.md"),
+                format!("---\nname: {}\nrequires: {}\n---\n", name, requires),
+            )
+            .unwrap();
+        }
+
+        let refused = remove_skill("k8s-conventions", &[], &source, false);
+        let still_there = source.join("k8s-conventions").exists();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(refused
+            .unwrap_err()
+            .to_string()
+            .contains("'k8s-conventions' is required by deploy"));
+        assert!(still_there);
+    }
+
+    #[test]
+    fn uninstall_removes_package_skills_and_keeps_modified_ones() {
+        let tmp = unique_tmp("uninstall");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join(".skillset").join("skills");
+        let target = tmp.join("target");
+        fs::create_dir_all(&target).unwrap();
+        let mut entries = Vec::new();
+        for (name, package) in [
+            ("uninstall-clean", "acme/skills@v1"),
+            ("uninstall-edited", "acme/skills@v1"),
+            ("uninstall-other", "globex/skills"),
+        ] {
+            let dir = source.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), format!("# {}", name)).unwrap();
+            symlink_dir(&dir, &target.join(name)).unwrap();
+            entries.push((
+                name.to_string(),
+                lockfile::LockedSkill {
+                    package: package.to_string(),
+                    git_ref: None,
+                    url: None,
+                    commit: None,
+                    subdir: None,
+                    skill: None,
+                    license: None,
+                    hash: tree_hash(&dir).unwrap(),
+                    files: Default::default(),
+                },
+            ));
+        }
+        lockfile::record(&source, entries).unwrap();
+        let mut manifest = manifest::Manifest::default();
+        for package in ["acme/skills@v1", "globex/skills"] {
+            manifest.add(PackageEntry {
+                package: package.to_string(),
+                ..Default::default()
+            });
+        }
+        manifest::save(&source, &manifest).unwrap();
+        fs::write(source.join("uninstall-edited").join("SKILL.md"), "# local").unwrap();
+        let targets = vec![("Tool".to_string(), target.clone())];
+
+        let unknown = uninstall_package("initech/skills", &targets, &source, true, false, false);
+        uninstall_package("acme/skills", &targets, &source, true, false, true).unwrap();
+        let previewed = source.join("uninstall-clean").exists()
+            && target.join("uninstall-clean").exists()
+            && lockfile::load(&source).unwrap().skills.len() == 3;
+        uninstall_package("acme/skills", &targets, &source, true, false, false).unwrap();
+        let lock = lockfile::load(&source).unwrap();
+        let packages: Vec<String> = manifest::load(&source)
+            .unwrap()
+            .packages
+            .into_iter()
+            .map(|entry| entry.package)
+            .collect();
+        let state = (
+            source.join("uninstall-clean").exists(),
+            target.join("uninstall-clean").exists(),
+            source.join("uninstall-edited").exists(),
+            target.join("uninstall-edited").exists(),
+            source.join("uninstall-other").exists(),
+        );
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(unknown
+            .unwrap_err()
+            .to_string()
+            .contains("is not installed"));
+        assert!(previewed);
+        assert_eq!(state, (false, false, true, true, true));
+        assert_eq!(
+            lock.skills.keys().collect::<Vec<_>>(),
+            vec!["uninstall-other"]
+        );
+        assert_eq!(packages, vec!["globex/skills"]);
+    }
+
+    #[test]
+    fn uninstall_with_dependents_needs_yes_not_remove_modified() {
+        let tmp = unique_tmp("uninstall_dependents");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join(".skillset").join("skills");
+        for (name, requires) in [("deploy", "[k8s-conventions]"), ("k8s-conventions", "[]")] {
+            fs::create_dir_all(source.join(name)).unwrap();
+            fs::write(
+                source.join(name).join("SKILL.md"),
+                format!("---\nname: {}\nrequires: {}\n---\n", name, requires),
+            )
+            .unwrap();
+        }
+        let locked = lockfile::LockedSkill {
+            package: "acme/k8s".to_string(),
+            git_ref: None,
+            url: None,
+            commit: None,
+            subdir: None,
+            skill: None,
+            license: None,
+            hash: tree_hash(&source.join("k8s-conventions")).unwrap(),
+            files: Default::default(),
+        };
+        lockfile::record(&source, vec![("k8s-conventions".to_string(), locked)]).unwrap();
+
+        let refused = uninstall_package("acme/k8s", &[], &source, false, true, false);
+        let kept = source.join("k8s-conventions").exists();
+        uninstall_package("acme/k8s", &[], &source, true, false, false).unwrap();
+        let removed = !source.join("k8s-conventions").exists();
+
+        fs::remove_dir_all(&tmp).ok();
+        let error = refused.unwrap_err().to_string();
+        assert!(error.contains("deploy (requires k8s-conventions)"));
+        assert!(error.contains("pass --yes"));
+        assert!(kept && removed);
+    }
 }
@@ -0,0 +1,722 @@
+use crate::config::{config_path, SigningConfig};
+use crate::hash::sha256_hex;
+use crate::install::{
+    canonical_repo_id, expand_home_path, git_output, glob_match, ResolvedPackage,
+};
+use crate::sparse;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Checksum manifest a package can publish with a detached signature next to it.
+pub const SUMS_FILE: &str = "SHA256SUMS";
+/// Detached SSH signature over the manifest (`ssh-keygen -Y sign -n skillset`).
+pub const SSH_SIG_FILE: &str = "SHA256SUMS.sig";
+/// Detached GPG signature over the manifest (`gpg --detach-sign --armor`).
+pub const GPG_SIG_FILE: &str = "SHA256SUMS.asc";
+/// Namespace SSH manifest signatures must be made with.
+const SSH_NAMESPACE: &str = "skillset";
+/// Principal trusted SSH keys are listed under in the generated allowed-signers file.
+const PRINCIPAL: &str = "skillset-trusted";
+
+/// Hex digits in a GPG long key ID, the shortest key reference accepted as trusted.
+const LONG_KEY_ID_LEN: usize = 16;
+
+/// Keys trusted for one package, split by kind.
+#[derive(Debug, Default, PartialEq)]
+pub struct TrustedKeys {
+    /// SSH public keys (`ssh-ed25519 AAAA...`)
+    pub ssh: Vec<String>,
+    /// GPG fingerprints or 16-digit long key IDs, uppercase without spaces
+    pub gpg: Vec<String>,
+}
+
+impl TrustedKeys {
+    pub fn is_empty(&self) -> bool {
+        self.ssh.is_empty() && self.gpg.is_empty()
+    }
+
+    /// Full fingerprints must match exactly; a long key ID matches the end of one.
+    fn trusts_gpg(&self, fingerprints: &[String]) -> bool {
+        self.gpg.iter().any(|key| {
+            fingerprints.iter().any(|fpr| {
+                if key.len() == LONG_KEY_ID_LEN {
+                    fpr.len() > key.len() && fpr.ends_with(key.as_str())
+                } else {
+                    fpr == key
+                }
+            })
+        })
+    }
+}
+
+/// Identity trust patterns are matched against: `host/owner/repo` for remote packages,
+/// `file/<path>` for local paths and archives.
+pub fn package_identity(spec: &str, package: &ResolvedPackage) -> String {
+    if let Some(url) = &package.url {
+        return canonical_repo_id(url);
+    }
+    let local = match spec.strip_prefix("file://") {
+        Some(path) => PathBuf::from(path),
+        None => expand_home_path(spec),
+    };
+    let local = local.canonicalize().unwrap_or(local);
+    canonical_repo_id(&format!("file://{}", local.display()))
+}
+
+/// True if a `trusted_keys` pattern covers `identity`. `owner/repo` is shorthand for
+/// `github.com/owner/repo`, and a bare host or path prefix covers everything under it.
+fn pattern_matches(pattern: &str, identity: &str) -> bool {
+    let pattern = pattern.trim().trim_end_matches('/');
+    let first = pattern.split('/').next().unwrap_or_default();
+    let pattern =
+        if pattern.contains('/') && !first.contains('.') && !first.contains('*') && first != "file"
+        {
+            format!("github.com/{}", pattern.to_lowercase())
+        } else {
+            pattern.to_string()
+        };
+    glob_match(&pattern, identity) || identity.starts_with(&format!("{}/", pattern))
+}
+
+fn is_ssh_key(key: &str) -> bool {
+    key.starts_with("ssh-") || key.starts_with("ecdsa-") || key.starts_with("sk-")
+}
+
+/// Normalise a configured GPG key to uppercase hex. Only 16-digit long key IDs and full
+/// fingerprints are accepted: shorter IDs are easy to collide with, and an empty key would
+/// match every signer.
+fn gpg_fingerprint(key: &str) -> Result<String> {
+    let compact: String = key.chars().filter(|c| !c.is_whitespace()).collect();
+    let fpr = compact
+        .strip_prefix("0x")
+        .or_else(|| compact.strip_prefix("0X"))
+        .unwrap_or(&compact)
+        .to_uppercase();
+    let valid = fpr.chars().all(|c| c.is_ascii_hexdigit())
+        && (fpr.len() == LONG_KEY_ID_LEN || fpr.len() == 40 || fpr.len() == 64);
+    if !valid {
+        anyhow::bail!(
+            "Invalid trusted key {:?}: expected an SSH public key or a GPG fingerprint\nHint: Use the full 40-character fingerprint from `gpg --fingerprint`.",
+            key
+        );
+    }
+    Ok(fpr)
+}
+
+/// Check every key under `trusted_keys`, so a malformed entry fails when the config loads
+/// instead of weakening a later signature check.
+pub fn check_keys(config: &SigningConfig) -> Result<()> {
+    for (pattern, keys) in &config.trusted_keys {
+        for key in keys {
+            let key = key.trim();
+            if !is_ssh_key(key) {
+                gpg_fingerprint(key).with_context(|| {
+                    format!(
+                        "Invalid install.signing.trusted_keys entry for {:?}",
+                        pattern
+                    )
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collect the keys of every `trusted_keys` pattern that covers `identity`.
+pub fn trusted_keys(config: &SigningConfig, identity: &str) -> Result<TrustedKeys> {
+    let mut keys = TrustedKeys::default();
+    for (pattern, patterns_keys) in &config.trusted_keys {
+        if !pattern_matches(pattern, identity) {
+            continue;
+        }
+        for key in patterns_keys {
+            let key = key.trim();
+            if is_ssh_key(key) {
+                keys.ssh.push(key.to_string());
+            } else {
+                keys.gpg.push(gpg_fingerprint(key)?);
+            }
+        }
+    }
+    keys.ssh.dedup();
+    keys.gpg.dedup();
+    Ok(keys)
+}
+
+/// Temporary SSH allowed-signers file listing the trusted keys; removed on drop.
+struct AllowedSigners {
+    path: PathBuf,
+}
+
+impl AllowedSigners {
+    fn new(keys: &TrustedKeys) -> Result<Self> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!(
+            "skillset-allowed-signers-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        let content: String = keys
+            .ssh
+            .iter()
+            .map(|key| format!("{} {}\n", PRINCIPAL, key))
+            .collect();
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for AllowedSigners {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Fingerprints from GPG `VALIDSIG` status lines (signing subkey and primary key).
+fn validsig_fingerprints(status: &str) -> Vec<String> {
+    status
+        .lines()
+        .filter_map(|line| line.strip_prefix("[GNUPG:] VALIDSIG "))
+        .flat_map(|rest| {
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+            let mut fprs = vec![fields.first().copied().unwrap_or_default().to_string()];
+            if fields.len() > 9 {
+                fprs.push(fields[fields.len() - 1].to_string());
+            }
+            fprs
+        })
+        .filter(|fpr| !fpr.is_empty())
+        .collect()
+}
+
+/// Interpret the output of a signature check. SSH signatures only verify against the
+/// allowed-signers file, so success means trusted; GPG signatures verify against the
+/// user's keyring, so the signing key must also be in the trusted list.
+fn judge(success: bool, output: &str, keys: &TrustedKeys, what: &str) -> Result<String> {
+    let fingerprints = validsig_fingerprints(output);
+    if !success {
+        anyhow::bail!(
+            "Signature on {} could not be verified with a trusted key:\n{}",
+            what,
+            output.trim()
+        );
+    }
+    if fingerprints.is_empty() {
+        let key = output
+            .lines()
+            .find_map(|line| line.split_once(" key ").map(|(_, key)| key.trim()))
+            .unwrap_or("trusted SSH key");
+        return Ok(format!("{} signed with SSH key {}", what, key));
+    }
+    if !keys.trusts_gpg(&fingerprints) {
+        anyhow::bail!(
+            "{} is signed by GPG key {}, which is not trusted for this package",
+            what,
+            fingerprints[0]
+        );
+    }
+    Ok(format!("{} signed with GPG key {}", what, fingerprints[0]))
+}
+
+/// Verify a signed tag (when `git_ref` names an annotated tag at HEAD) or the HEAD commit.
+/// Returns None when neither is signed.
+fn verify_git(repo: &Path, git_ref: Option<&str>, keys: &TrustedKeys) -> Result<Option<String>> {
+    let head = git_output(repo, &["rev-parse", "HEAD"]);
+    let tag = git_ref
+        .map(|r| format!("refs/tags/{}", r))
+        .filter(|tag| git_output(repo, &["cat-file", "-t", tag]).as_deref() == Some("tag"))
+        .filter(|tag| git_output(repo, &["rev-parse", &format!("{}^{{commit}}", tag)]) == head)
+        .filter(|tag| {
+            git_output(repo, &["cat-file", "tag", tag])
+                .is_some_and(|body| body.contains("-----BEGIN"))
+        });
+
+    let (command, object, what) = match &tag {
+        Some(tag) => (
+            "verify-tag",
+            tag.clone(),
+            format!("tag {}", git_ref.unwrap_or_default()),
+        ),
+        None => {
+            let signed = git_output(repo, &["cat-file", "commit", "HEAD"])
+                .is_some_and(|body| body.lines().any(|line| line.starts_with("gpgsig")));
+            if !signed {
+                return Ok(None);
+            }
+            let short = head
+                .as_deref()
+                .map(|c| &c[..c.len().min(7)])
+                .unwrap_or("HEAD");
+            (
+                "verify-commit",
+                "HEAD".to_string(),
+                format!("commit {}", short),
+            )
+        }
+    };
+
+    let signers = AllowedSigners::new(keys)?;
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .arg("-c")
+        .arg(format!(
+            "gpg.ssh.allowedSignersFile={}",
+            signers.path.display()
+        ))
+        .args([command, "--raw", &object])
+        .output()
+        .context("Failed to run git. Is git installed?")?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stderr),
+        String::from_utf8_lossy(&output.stdout)
+    );
+    judge(output.status.success(), &text, keys, &what).map(Some)
+}
+
+/// Parse a `sha256sum`-style manifest into relative path -> hex digest.
+fn parse_sums(content: &str) -> Result<BTreeMap<String, String>> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (digest, path) = line
+                .split_once(char::is_whitespace)
+                .with_context(|| format!("Malformed {} line: {}", SUMS_FILE, line))?;
+            let path = path.trim_start().trim_start_matches('*');
+            let path = path.strip_prefix("./").unwrap_or(path);
+            Ok((path.to_string(), digest.to_lowercase()))
+        })
+        .collect()
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if relative == ".git"
+            || [SUMS_FILE, SSH_SIG_FILE, GPG_SIG_FILE].contains(&relative.as_str())
+        {
+            continue;
+        }
+        // A symlinked directory is listed rather than followed, so it must be in the
+        // signed manifest like any file and can't loop back up the tree.
+        let is_symlink = fs::symlink_metadata(&path)
+            .map(|meta| meta.file_type().is_symlink())
+            .unwrap_or(false);
+        if path.is_dir() && !is_symlink {
+            collect_files(root, &path, files)?;
+        } else {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Check every file under `dir` against the manifest: nothing unlisted, changed, or missing.
+fn check_sums(dir: &Path, sums: &BTreeMap<String, String>) -> Result<()> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    for file in &files {
+        let Some(expected) = sums.get(file) else {
+            anyhow::bail!("{} is not listed in the signed {}", file, SUMS_FILE);
+        };
+        let path = dir.join(file);
+        let contents =
+            fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        if &sha256_hex(&contents) != expected {
+            anyhow::bail!("{} does not match the signed {}", file, SUMS_FILE);
+        }
+    }
+    // Files a sparse clone hasn't checked out are still in the signed commit.
+    let sparse = dir
+        .ancestors()
+        .find(|d| d.join(".git").exists())
+        .is_some_and(sparse::is_sparse);
+    let not_checked_out = |path: &str| sparse && sparse::in_head(dir, &format!("./{}", path));
+    if let Some(missing) = sums
+        .keys()
+        .find(|path| !files.contains(path) && !not_checked_out(path))
+    {
+        anyhow::bail!(
+            "{} is listed in the signed {} but missing",
+            missing,
+            SUMS_FILE
+        );
+    }
+    Ok(())
+}
+
+/// Verify a detached signature over `SHA256SUMS` in `dir`, then the files it lists.
+/// Returns None when the directory has no signed manifest.
+fn verify_manifest(dir: &Path, keys: &TrustedKeys) -> Result<Option<String>> {
+    let sums_path = dir.join(SUMS_FILE);
+    let ssh_sig = dir.join(SSH_SIG_FILE);
+    let gpg_sig = dir.join(GPG_SIG_FILE);
+    if !sums_path.is_file() || !(ssh_sig.is_file() || gpg_sig.is_file()) {
+        return Ok(None);
+    }
+    let content =
+        fs::read(&sums_path).with_context(|| format!("Failed to read {}", sums_path.display()))?;
+
+    let what = SUMS_FILE.to_string();
+    let description = if ssh_sig.is_file() {
+        let signers = AllowedSigners::new(keys)?;
+        let mut child = Command::new("ssh-keygen")
+            .args(["-Y", "verify", "-n", SSH_NAMESPACE, "-I", PRINCIPAL, "-f"])
+            .arg(&signers.path)
+            .arg("-s")
+            .arg(&ssh_sig)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run ssh-keygen. Is OpenSSH installed?")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(&content)?;
+        }
+        let output = child.wait_with_output()?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        judge(output.status.success(), &text, keys, &what)?
+    } else {
+        let output = Command::new("gpg")
+            .args(["--batch", "--status-fd", "1", "--verify"])
+            .arg(&gpg_sig)
+            .arg(&sums_path)
+            .output()
+            .context("Failed to run gpg. Is GnuPG installed?")?;
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        judge(output.status.success(), &text, keys, &what)?
+    };
+
+    let content = String::from_utf8_lossy(&content);
+    check_sums(dir, &parse_sums(&content)?)?;
+    Ok(Some(description))
+}
+
+/// Look for a trusted signature on a resolved package: a signed tag or commit for Git
+/// packages, otherwise a signed `SHA256SUMS` in the package directory or its root.
+/// Returns None when the package is unsigned; fails when a signature is present but bad.
+pub fn verify_package(
+    package: &ResolvedPackage,
+    git_ref: Option<&str>,
+    keys: &TrustedKeys,
+) -> Result<Option<String>> {
+    if package.is_git() {
+        if let Some(verified) = verify_git(&package.root, git_ref, keys)? {
+            return Ok(Some(verified));
+        }
+    }
+    if let Some(verified) = verify_manifest(&package.path, keys)? {
+        return Ok(Some(verified));
+    }
+    if package.root != package.path {
+        return verify_manifest(&package.root, keys);
+    }
+    Ok(None)
+}
+
+/// Apply the signing policy to a resolved package before any of its skills are installed.
+/// Packages with trusted keys configured must carry a valid signature from one of them if
+/// they are signed at all; with `require_signed`, every package must.
+pub fn enforce(
+    spec: &str,
+    package: &ResolvedPackage,
+    git_ref: Option<&str>,
+    config: &SigningConfig,
+) -> Result<()> {
+    let identity = package_identity(spec, package);
+    let keys = trusted_keys(config, &identity)?;
+    if keys.is_empty() {
+        if config.require_signed {
+            anyhow::bail!(
+                "Signatures are required but no trusted keys are configured for {}\nHint: Add its signing key under install.signing.trusted_keys in {}.",
+                identity,
+                config_path()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|_| "the config file".to_string())
+            );
+        }
+        return Ok(());
+    }
+
+    match verify_package(package, git_ref, &keys)
+        .with_context(|| format!("Signature check failed for {}", spec))?
+    {
+        Some(description) => println!("Verified signature: {}", description),
+        None if config.require_signed => anyhow::bail!(
+            "{} is not signed and signatures are required\nHint: Install a signed tag with --ref, or publish a signed {} in the package.",
+            spec,
+            SUMS_FILE
+        ),
+        None => eprintln!(
+            "Warning: {} is not signed, although trusted keys are configured for it.",
+            spec
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_signature_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn run(dir: &Path, program: &str, args: &[&str]) {
+        let status = Command::new(program)
+            .current_dir(dir)
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "{} {:?} failed", program, args);
+    }
+
+    /// Generate an ed25519 key pair and return (private key path, public key line).
+    fn keygen(dir: &Path, name: &str) -> (PathBuf, String) {
+        let key = dir.join(name);
+        run(
+            dir,
+            "ssh-keygen",
+            &[
+                "-q",
+                "-t",
+                "ed25519",
+                "-N",
+                "",
+                "-C",
+                name,
+                "-f",
+                key.to_str().unwrap(),
+            ],
+        );
+        let public = fs::read_to_string(key.with_extension("pub")).unwrap();
+        (key, public.trim().to_string())
+    }
+
+    fn keys_for(public: &str) -> TrustedKeys {
+        TrustedKeys {
+            ssh: vec![public.to_string()],
+            gpg: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn trusted_keys_match_package_host_and_glob_patterns() {
+        let mut config = SigningConfig::default();
+        config.trusted_keys.insert(
+            "Acme/Skills".to_string(),
+            vec!["ssh-ed25519 AAAA1".to_string()],
+        );
+        config.trusted_keys.insert(
+            "git.example.com".to_string(),
+            vec!["ssh-ed25519 AAAA2".to_string()],
+        );
+        config.trusted_keys.insert(
+            "github.com/corp/*".to_string(),
+            vec!["0xabcd 1234 ABCD 1234".to_string()],
+        );
+
+        let acme = trusted_keys(&config, "github.com/acme/skills").unwrap();
+        let host = trusted_keys(&config, "git.example.com/team/repo").unwrap();
+        let corp = trusted_keys(&config, "github.com/corp/tools").unwrap();
+        let other = trusted_keys(&config, "github.com/acme/other").unwrap();
+
+        assert_eq!(acme.ssh, vec!["ssh-ed25519 AAAA1"]);
+        assert_eq!(host.ssh, vec!["ssh-ed25519 AAAA2"]);
+        assert_eq!(corp.gpg, vec!["ABCD1234ABCD1234"]);
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_and_short_gpg_keys() {
+        let config_with = |key: &str| {
+            let mut config = SigningConfig::default();
+            config
+                .trusted_keys
+                .insert("acme/skills".to_string(), vec![key.to_string()]);
+            config
+        };
+        let fpr = "0123456789ABCDEF0123456789ABCDEF01234567".to_string();
+        let keys = TrustedKeys {
+            ssh: Vec::new(),
+            gpg: vec![fpr.clone()],
+        };
+        let long_id = TrustedKeys {
+            ssh: Vec::new(),
+            gpg: vec![fpr[24..].to_string()],
+        };
+        let other = format!("FF{}", &fpr[2..]);
+
+        assert!(check_keys(&config_with("")).is_err());
+        assert!(check_keys(&config_with("  ")).is_err());
+        assert!(check_keys(&config_with("1234")).is_err());
+        assert!(check_keys(&config_with("0x89ABCDEF01234567")).is_ok());
+        assert!(trusted_keys(&config_with("1234"), "github.com/acme/skills").is_err());
+        assert!(keys.trusts_gpg(std::slice::from_ref(&fpr)));
+        assert!(!keys.trusts_gpg(std::slice::from_ref(&other)));
+        assert!(long_id.trusts_gpg(std::slice::from_ref(&other)));
+        assert!(!long_id.trusts_gpg(&[fpr[24..].to_string()]));
+    }
+
+    #[test]
+    fn verifies_ssh_signed_commits_and_tags() {
+        let tmp = unique_tmp("git");
+        let _ = fs::remove_dir_all(&tmp);
+        fs::create_dir_all(&tmp).unwrap();
+        let (key, public) = keygen(&tmp, "signer");
+        let (_, stranger) = keygen(&tmp, "stranger");
+
+        let origin = tmp.join("origin");
+        fs::create_dir_all(origin.join("skills").join("a")).unwrap();
+        fs::write(origin.join("skills").join("a").join("SKILL.md"), "# A").unwrap();
+        run(&origin, "git", &["init", "-q"]);
+        for (name, value) in [
+            ("user.name", "Test"),
+            ("user.email", "test@example.com"),
+            ("gpg.format", "ssh"),
+            ("user.signingkey", key.to_str().unwrap()),
+        ] {
+            run(&origin, "git", &["config", name, value]);
+        }
+        run(&origin, "git", &["add", "."]);
+        run(&origin, "git", &["commit", "-q", "-m", "unsigned"]);
+        run(
+            &tmp,
+            "git",
+            &["clone", "-q", "--bare", "origin", "unsigned.git"],
+        );
+        run(
+            &origin,
+            "git",
+            &["commit", "-q", "-S", "--allow-empty", "-m", "signed"],
+        );
+        run(&origin, "git", &["tag", "-s", "v1", "-m", "v1"]);
+        run(
+            &tmp,
+            "git",
+            &["clone", "-q", "--bare", "origin", "bare.git"],
+        );
+
+        // Shallow clones like the package cache makes.
+        let clone = |name: &str, bare: &str, branch: Option<&str>| {
+            let url = format!("file://{}", tmp.join(bare).display());
+            let mut args = vec!["clone", "-q", "--depth", "1"];
+            if let Some(branch) = branch {
+                args.extend(["--branch", branch]);
+            }
+            args.extend([url.as_str(), name]);
+            run(&tmp, "git", &args);
+            ResolvedPackage {
+                path: tmp.join(name),
+                root: tmp.join(name),
+                url: Some(url),
+            }
+        };
+        let tagged = clone("tagged", "bare.git", Some("v1"));
+        let head = clone("head", "bare.git", None);
+        let unsigned = clone("plain", "unsigned.git", None);
+
+        let tag = verify_package(&tagged, Some("v1"), &keys_for(&public));
+        let commit = verify_package(&head, None, &keys_for(&public));
+        let untrusted = verify_package(&head, None, &keys_for(&stranger));
+        let none = verify_package(&unsigned, None, &keys_for(&public));
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(tag
+            .unwrap()
+            .unwrap()
+            .starts_with("tag v1 signed with SSH key"));
+        assert!(commit.unwrap().unwrap().starts_with("commit "));
+        assert!(untrusted.is_err());
+        assert_eq!(none.unwrap(), None);
+    }
+
+    #[test]
+    fn verifies_signed_checksum_manifest() {
+        let tmp = unique_tmp("manifest");
+        let _ = fs::remove_dir_all(&tmp);
+        fs::create_dir_all(&tmp).unwrap();
+        let (key, public) = keygen(&tmp, "signer");
+        let package_dir = tmp.join("package");
+        let skill = package_dir.join("skills").join("a");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# A").unwrap();
+        fs::write(
+            package_dir.join(SUMS_FILE),
+            format!("{}  ./skills/a/SKILL.md\n", sha256_hex(b"# A")),
+        )
+        .unwrap();
+        run(
+            &package_dir,
+            "ssh-keygen",
+            &[
+                "-Y",
+                "sign",
+                "-n",
+                SSH_NAMESPACE,
+                "-f",
+                key.to_str().unwrap(),
+                SUMS_FILE,
+            ],
+        );
+        let package = ResolvedPackage {
+            path: package_dir.clone(),
+            root: package_dir.clone(),
+            url: None,
+        };
+        let mut config = SigningConfig {
+            require_signed: true,
+            ..Default::default()
+        };
+
+        let no_keys = enforce(package_dir.to_str().unwrap(), &package, None, &config);
+        config.trusted_keys.insert(
+            format!("file{}", package_dir.canonicalize().unwrap().display()),
+            vec![public.clone()],
+        );
+        let trusted = enforce(package_dir.to_str().unwrap(), &package, None, &config);
+        fs::write(skill.join("extra.md"), "unlisted").unwrap();
+        let unlisted = verify_package(&package, None, &keys_for(&public));
+        fs::remove_file(skill.join("extra.md")).unwrap();
+        fs::write(skill.join("SKILL.md"), "# Tampered").unwrap();
+        let tampered = verify_package(&package, None, &keys_for(&public));
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(no_keys
+            .unwrap_err()
+            .to_string()
+            .contains("no trusted keys are configured"));
+        assert!(trusted.is_ok());
+        assert!(format!("{:#}", unlisted.unwrap_err()).contains("not listed"));
+        assert!(format!("{:#}", tampered.unwrap_err()).contains("does not match"));
+    }
+}
@@ -1,5 +1,10 @@
+use crate::hash::tree_hash;
+use crate::managed::{self, Kept, Ownership};
+use crate::preview::{compare, Change};
+use crate::reconcile::{self, Divergence, Resolution};
 use crate::registry;
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -128,6 +133,76 @@ mod tests {
 
         fs::remove_dir_all(&tmp).ok();
     }
+
+    #[test]
+    fn test_copied_skill_changes_outside_skill_md_are_detected() {
+        let tmp = unique_tmp("copy_tree_changes");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join("source").join("my-skill");
+        let target = tmp.join("target").join("my-skill");
+        fs::create_dir_all(source.join("scripts")).unwrap();
+        fs::write(source.join("SKILL.md"), "# My Skill").unwrap();
+        fs::write(source.join("scripts").join("run.sh"), "echo v1").unwrap();
+        copy_skill(&source, &target).unwrap();
+        let copied = skill_target_unchanged(&source, &target, SyncMethod::Copy);
+
+        fs::write(source.join("scripts").join("run.sh"), "echo v2").unwrap();
+        let script_changed = skill_target_unchanged(&source, &target, SyncMethod::Copy);
+        copy_skill(&source, &target).unwrap();
+        fs::write(source.join("reference.md"), "# Reference").unwrap();
+        let file_added = skill_target_unchanged(&source, &target, SyncMethod::Copy);
+        let changes = compare(&source, &target).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(copied);
+        assert!(!script_changed);
+        assert!(!file_added);
+        assert_eq!(
+            changes,
+            Change::Changed {
+                modified: vec![],
+                added: vec!["reference.md".to_string()],
+                removed: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_orphaned_skills_only_lists_links_into_source() {
+        let tmp = unique_tmp("orphaned");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join("source");
+        let elsewhere = tmp.join("elsewhere");
+        let target = tmp.join("target");
+        for dir in [
+            source.join("kept"),
+            source.join("gone"),
+            elsewhere.join("other"),
+        ] {
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), "# Skill").unwrap();
+        }
+        symlink_skill(&source.join("kept"), &target.join("kept")).unwrap();
+        symlink_skill(&source.join("gone"), &target.join("gone")).unwrap();
+        symlink_skill(&elsewhere.join("other"), &target.join("other")).unwrap();
+        fs::create_dir_all(target.join("mine")).unwrap();
+        fs::write(target.join("mine").join("SKILL.md"), "# Mine").unwrap();
+        fs::remove_dir_all(source.join("gone")).unwrap();
+
+        let targets = vec![("Claude".to_string(), target.clone())];
+        let orphans = orphaned_skills(&source, &targets).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(
+            orphans,
+            vec![(
+                "Claude".to_string(),
+                "gone".to_string(),
+                target.join("gone"),
+                Ownership::Managed
+            )]
+        );
+    }
 }
 
 /// Copy an entire skill directory from source to target.
@@ -186,7 +261,8 @@ fn create_dir_symlink(from: &Path, to: &Path) -> Result<()> {
     std::os::windows::fs::symlink_dir(from, to).context("Failed to create directory symlink")
 }
 
-/// Recursively copy a directory.
+/// Recursively copy a directory. Symlinked directories are recreated as links rather than
+/// followed, so a link back up the tree can't recurse forever.
 fn copy_dir_recursive(from: &Path, to: &Path) -> Result<()> {
     if !from.is_dir() {
         fs::copy(from, to).context("Failed to copy file")?;
@@ -199,6 +275,11 @@ fn copy_dir_recursive(from: &Path, to: &Path) -> Result<()> {
         let entry = entry?;
         let src = entry.path();
         let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_symlink() && src.is_dir() {
+            let link = fs::read_link(&src).context("Failed to read symlink")?;
+            create_dir_symlink(&link, &dest)?;
+            continue;
+        }
         copy_dir_recursive(&src, &dest)?;
     }
 
@@ -213,14 +294,15 @@ pub enum OverwritePolicy {
 }
 
 /// How skills should be written to target directories.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SyncMethod {
     Copy,
     Symlink,
 }
 
 impl SyncMethod {
-    fn action(self) -> &'static str {
+    pub fn action(self) -> &'static str {
         match self {
             SyncMethod::Copy => "copy",
             SyncMethod::Symlink => "symlink",
@@ -242,7 +324,8 @@ impl SyncMethod {
     }
 }
 
-/// Check if a skill's content is identical between source and target by comparing SKILL.md.
+/// Check if a copied skill is identical to the source: same files, contents and executable
+/// bits (see `hash::tree_hash`).
 fn skill_unchanged(source: &Path, target: &Path) -> bool {
     if fs::symlink_metadata(target)
         .map(|metadata| metadata.file_type().is_symlink())
@@ -251,15 +334,8 @@ fn skill_unchanged(source: &Path, target: &Path) -> bool {
         return false;
     }
 
-    let source_md = source.join("SKILL.md");
-    let target_md = target.join("SKILL.md");
-
-    if !source_md.exists() || !target_md.exists() {
-        return false;
-    }
-
-    match (fs::read(&source_md), fs::read(&target_md)) {
-        (Ok(src), Ok(tgt)) => src == tgt,
+    match (tree_hash(source), tree_hash(target)) {
+        (Ok(source_hash), Ok(target_hash)) => source_hash == target_hash,
         _ => false,
     }
 }
@@ -294,7 +370,7 @@ fn skill_symlink_unchanged(source: &Path, target: &Path) -> bool {
     }
 }
 
-fn skill_target_unchanged(source: &Path, target: &Path, method: SyncMethod) -> bool {
+pub fn skill_target_unchanged(source: &Path, target: &Path, method: SyncMethod) -> bool {
     match method {
         SyncMethod::Copy => skill_unchanged(source, target),
         SyncMethod::Symlink => skill_symlink_unchanged(source, target),
@@ -308,28 +384,20 @@ fn write_skill(source: &Path, target: &Path, method: SyncMethod) -> Result<()> {
     }
 }
 
-/// Show a unified diff of two files using the `similar` crate.
-fn show_diff(source_path: &Path, target_path: &Path, skill_name: &str, label: &str) {
-    let source_content = match fs::read_to_string(source_path) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Warning: could not read {}: {}", source_path.display(), e);
-            return;
-        }
-    };
-    let target_content = match fs::read_to_string(target_path) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Warning: could not read {}: {}", target_path.display(), e);
-            return;
-        }
+/// Show a unified diff of one file in a skill using the `similar` crate.
+fn show_file_diff(source: &Path, target: &Path, skill_name: &str, label: &str, file: &str) {
+    let source_path = source.join(file);
+    let target_path = target.join(file);
+    let (Ok(source_content), Ok(target_content)) = (
+        fs::read_to_string(&source_path),
+        fs::read_to_string(&target_path),
+    ) else {
+        println!("    (binary file {} differs)", file);
+        return;
     };
 
     if source_content == target_content {
-        println!(
-            "    (no changes in SKILL.md for {} at {})",
-            skill_name, label
-        );
+        println!("    (only the executable bit of {} changed)", file);
         return;
     }
 
@@ -337,10 +405,10 @@ fn show_diff(source_path: &Path, target_path: &Path, skill_name: &str, label: &s
     let diff = TextDiff::from_lines(&target_content, &source_content);
 
     println!(
-        "    --- {}/{}/SKILL.md (target: {})",
-        label, skill_name, label
+        "    --- {}/{}/{} (target: {})",
+        label, skill_name, file, label
     );
-    println!("    +++ {}/{}/SKILL.md (source)", label, skill_name);
+    println!("    +++ {}/{}/{} (source)", label, skill_name, file);
 
     for change in diff.iter_all_changes() {
         let sign = match change.tag() {
@@ -353,6 +421,191 @@ fn show_diff(source_path: &Path, target_path: &Path, skill_name: &str, label: &s
     println!();
 }
 
+/// Show what overwriting the skill directory `target` with `source` would change: a diff of
+/// every modified file, plus files only in the source or only in the target.
+pub fn show_diff(source: &Path, target: &Path, skill_name: &str, label: &str) {
+    let (modified, added, removed) = match compare(source, target) {
+        Ok(Change::Changed {
+            modified,
+            added,
+            removed,
+        }) => (modified, added, removed),
+        Ok(_) => {
+            println!("    (no changes in {} at {})", skill_name, label);
+            return;
+        }
+        Err(e) => {
+            eprintln!("Warning: could not compare {}: {}", skill_name, e);
+            return;
+        }
+    };
+
+    for file in &modified {
+        show_file_diff(source, target, skill_name, label, file);
+    }
+    for file in &added {
+        println!("    + {} (new in source)", file);
+    }
+    for file in &removed {
+        println!("    - {} (only in target, will be removed)", file);
+    }
+}
+
+/// Note a synced skill in the registry and in its target's ownership manifest. Failures only
+/// warn: the skill itself was written.
+pub fn record_sync(
+    skill_name: &str,
+    skill_target: &Path,
+    label: &str,
+    source: &Path,
+    method: SyncMethod,
+    hash: &str,
+) {
+    if let Err(e) = registry::record(skill_name, &skill_target.to_string_lossy(), label) {
+        eprintln!(
+            "Warning: failed to update registry for '{}': {}",
+            skill_name, e
+        );
+    }
+    let target_dir = skill_target.parent().unwrap_or(skill_target);
+    if let Err(e) = managed::record(target_dir, skill_name, source, method, hash) {
+        eprintln!(
+            "Warning: failed to update {} for '{}': {}",
+            managed::MANAGED_FILE,
+            skill_name,
+            e
+        );
+    }
+    if method == SyncMethod::Copy {
+        if let Err(e) = reconcile::save_base(&source.join(skill_name), hash) {
+            eprintln!("Warning: failed to snapshot '{}': {}", skill_name, e);
+        }
+    }
+}
+
+/// Outcome of reconciling a diverged target (see `sync_diverged`).
+enum Reconciled {
+    /// The source is unchanged (a conflicted merge only writes to the target)
+    Unchanged,
+    /// The target's edits were merged cleanly; carries the source's new tree hash
+    Merged(String),
+}
+
+/// Reconcile a copied skill that was edited in its target since the last sync: keep the
+/// target, take the source, or merge the target's edits into the source and copy the result.
+/// Only `force` takes the source without asking. A target the user kept before is left alone
+/// until it or the source changes again.
+fn sync_diverged(
+    skill_name: &str,
+    skill_target: &Path,
+    label: &str,
+    source: &Path,
+    divergence: &Divergence,
+    force: bool,
+) -> Result<Reconciled> {
+    let skill_source = source.join(skill_name);
+    if divergence.kept && !force {
+        println!(
+            "  Kept {} at {} (edited there; unchanged since you chose to keep it)",
+            skill_name, label
+        );
+        return Ok(Reconciled::Unchanged);
+    }
+    let resolution = if force {
+        Resolution::TakeSource
+    } else {
+        reconcile::ask(skill_name, label, &skill_source, skill_target, divergence)?
+    };
+    match (resolution, &divergence.base) {
+        (Resolution::Merge, Some(base)) => {
+            let merged = std::env::temp_dir().join(format!(
+                "skillset-merge-{}-{}",
+                skill_name,
+                std::process::id()
+            ));
+            let result = reconcile::merge(base, skill_target, &skill_source, &merged, label)
+                .and_then(|conflicts| {
+                    let dest = if conflicts.is_empty() {
+                        &skill_source
+                    } else {
+                        skill_target
+                    };
+                    copy_skill(&merged, dest)?;
+                    Ok(conflicts)
+                });
+            let _ = fs::remove_dir_all(&merged);
+            let conflicts = result?;
+            if !conflicts.is_empty() {
+                println!(
+                    "    Merged {} at {} with conflicts in: {}",
+                    skill_name,
+                    label,
+                    conflicts.join(", ")
+                );
+                println!(
+                    "    The source is unchanged. Resolve the conflicts in {}, then run `skillset pull {} --from \"{}\"`.",
+                    skill_target.display(),
+                    skill_name,
+                    label
+                );
+                return Ok(Reconciled::Unchanged);
+            }
+            let hash = tree_hash(&skill_source)?;
+            copy_skill(&skill_source, skill_target)?;
+            record_sync(
+                skill_name,
+                skill_target,
+                label,
+                source,
+                SyncMethod::Copy,
+                &hash,
+            );
+            println!(
+                "    Merged {} at {} into the source and copied the result back",
+                skill_name, label
+            );
+            Ok(Reconciled::Merged(hash))
+        }
+        (Resolution::TakeSource, _) => {
+            let hash = tree_hash(&skill_source)?;
+            copy_skill(&skill_source, skill_target)?;
+            record_sync(
+                skill_name,
+                skill_target,
+                label,
+                source,
+                SyncMethod::Copy,
+                &hash,
+            );
+            println!(
+                "  Overwrote {} at {} (edits made there were discarded)",
+                skill_name, label
+            );
+            Ok(Reconciled::Unchanged)
+        }
+        _ => {
+            let kept = Kept {
+                target: tree_hash(skill_target)?,
+                source: tree_hash(&skill_source)?,
+            };
+            let target_dir = skill_target.parent().unwrap_or(skill_target);
+            if let Err(e) = managed::keep(target_dir, skill_name, kept) {
+                eprintln!(
+                    "Warning: failed to update {} for '{}': {}",
+                    managed::MANAGED_FILE,
+                    skill_name,
+                    e
+                );
+            }
+            println!(
+                "    Kept {} at {}. Use `skillset pull {} --from \"{}\"` to bring its edits into the source.",
+                skill_name, label, skill_name, label
+            );
+            Ok(Reconciled::Unchanged)
+        }
+    }
+}
+
 /// Sync skills from source to multiple targets.
 /// Creates each target dir if it doesn't exist, then copies skills. Prompts when a skill already exists.
 pub fn sync_skills(
@@ -378,16 +631,35 @@ pub fn sync_skills(
     }
 
     println!("Found {} skill(s) to sync:", skills.len());
+    crate::deps::warn_missing(source)?;
+    // Only --force starts out as `All`; answering "all" later doesn't cover diverged skills.
+    let forced = matches!(*user_policy, OverwritePolicy::All);
 
     for skill_name in &skills {
         let skill_source = source.join(skill_name);
+        let mut hash = if dry_run {
+            String::new()
+        } else {
+            tree_hash(&skill_source)?
+        };
 
         for (label, target_path) in targets {
             let skill_target = target_path.join(skill_name);
             let exists = skill_target.exists();
 
+            let diverged = if exists && method == SyncMethod::Copy {
+                reconcile::divergence(target_path, skill_name, source)
+            } else {
+                None
+            };
+
             if dry_run {
-                if exists {
+                if diverged.is_some() {
+                    println!(
+                        "[DRY RUN]   {} at {} was edited there since the last sync; would ask to keep it, take the source, or merge",
+                        skill_name, label
+                    );
+                } else if exists {
                     println!(
                         "[DRY RUN]   Would {} {} at {}",
                         method.action(),
@@ -395,11 +667,7 @@ pub fn sync_skills(
                         label
                     );
                     if show_diffs {
-                        let source_md = skill_source.join("SKILL.md");
-                        let target_md = skill_target.join("SKILL.md");
-                        if source_md.exists() && target_md.exists() {
-                            show_diff(&source_md, &target_md, skill_name, label);
-                        }
+                        show_diff(&skill_source, &skill_target, skill_name, label);
                     }
                 } else {
                     println!(
@@ -415,28 +683,36 @@ pub fn sync_skills(
             if exists {
                 // Skip if target already matches the selected sync method.
                 if skill_target_unchanged(&skill_source, &skill_target, method) {
+                    record_sync(skill_name, &skill_target, label, source, method, &hash);
                     continue;
                 }
 
-                if show_diffs {
-                    let source_md = skill_source.join("SKILL.md");
-                    let target_md = skill_target.join("SKILL.md");
-                    if source_md.exists() && target_md.exists() {
-                        show_diff(&source_md, &target_md, skill_name, label);
+                if let Some(divergence) = &diverged {
+                    if show_diffs {
+                        show_diff(&skill_source, &skill_target, skill_name, label);
+                    }
+                    match sync_diverged(
+                        skill_name,
+                        &skill_target,
+                        label,
+                        source,
+                        divergence,
+                        forced,
+                    )? {
+                        Reconciled::Unchanged => {}
+                        Reconciled::Merged(merged) => hash = merged,
                     }
+                    continue;
+                }
+
+                if show_diffs {
+                    show_diff(&skill_source, &skill_target, skill_name, label);
                 }
 
                 match *user_policy {
                     OverwritePolicy::All => {
                         write_skill(&skill_source, &skill_target, method)?;
-                        if let Err(e) =
-                            registry::record(skill_name, &skill_target.to_string_lossy(), label)
-                        {
-                            eprintln!(
-                                "Warning: failed to update registry for '{}': {}",
-                                skill_name, e
-                            );
-                        }
+                        record_sync(skill_name, &skill_target, label, source, method, &hash);
                         println!("  {} {} at {}", method.overwrite_tense(), skill_name, label);
                     }
                     OverwritePolicy::PerSkill => {
@@ -456,31 +732,27 @@ pub fn sync_skills(
                         match input.as_str() {
                             "y" | "yes" => {
                                 write_skill(&skill_source, &skill_target, method)?;
-                                if let Err(e) = registry::record(
+                                record_sync(
                                     skill_name,
-                                    &skill_target.to_string_lossy(),
+                                    &skill_target,
                                     label,
-                                ) {
-                                    eprintln!(
-                                        "Warning: failed to update registry for '{}': {}",
-                                        skill_name, e
-                                    );
-                                }
+                                    source,
+                                    method,
+                                    &hash,
+                                );
                                 println!("    {} to {}", method.past_tense(), label);
                             }
                             "a" | "all" => {
                                 *user_policy = OverwritePolicy::All;
                                 write_skill(&skill_source, &skill_target, method)?;
-                                if let Err(e) = registry::record(
+                                record_sync(
                                     skill_name,
-                                    &skill_target.to_string_lossy(),
+                                    &skill_target,
                                     label,
-                                ) {
-                                    eprintln!(
-                                        "Warning: failed to update registry for '{}': {}",
-                                        skill_name, e
-                                    );
-                                }
+                                    source,
+                                    method,
+                                    &hash,
+                                );
                                 println!(
                                     "    {} to {} (will overwrite rest)",
                                     method.past_tense(),
@@ -495,13 +767,7 @@ pub fn sync_skills(
                 }
             } else {
                 write_skill(&skill_source, &skill_target, method)?;
-                if let Err(e) = registry::record(skill_name, &skill_target.to_string_lossy(), label)
-                {
-                    eprintln!(
-                        "Warning: failed to update registry for '{}': {}",
-                        skill_name, e
-                    );
-                }
+                record_sync(skill_name, &skill_target, label, source, method, &hash);
                 println!("  {} {} to {}", method.past_tense(), skill_name, label);
             }
         }
@@ -514,3 +780,107 @@ pub fn sync_skills(
     }
     Ok(())
 }
+
+/// Skills in `targets` that skillset synced from `source` but that are no longer in it, as
+/// (label, skill name, path, ownership). Ownership comes from each target's manifest (see
+/// `managed::ownership`), so skills added to a tool directly are left out; modified ones are
+/// included so callers can report them.
+pub fn orphaned_skills(
+    source: &Path,
+    targets: &[(String, PathBuf)],
+) -> Result<Vec<(String, String, PathBuf, Ownership)>> {
+    let present = discover_skills(source)?;
+
+    let mut orphans = Vec::new();
+    for (label, target_path) in targets {
+        let Ok(entries) = fs::read_dir(target_path) else {
+            continue;
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|name| !name.starts_with('.') && !present.contains(name))
+            .collect();
+        names.sort();
+        for name in names {
+            let ownership = managed::ownership(target_path, &name, source);
+            if ownership != Ownership::Foreign {
+                let path = target_path.join(&name);
+                orphans.push((label.clone(), name, path, ownership));
+            }
+        }
+    }
+    Ok(orphans)
+}
+
+/// Remove orphaned skills (see `orphaned_skills`) from the targets, asking for each one
+/// unless the policy is `All`. Skills changed in the target since they were synced are kept.
+pub fn prune_skills(
+    source: &Path,
+    targets: &[(String, PathBuf)],
+    user_policy: &mut OverwritePolicy,
+    dry_run: bool,
+) -> Result<()> {
+    let orphans = orphaned_skills(source, targets)?;
+    if orphans.is_empty() {
+        println!("No orphaned skills to prune.");
+        return Ok(());
+    }
+
+    println!(
+        "Found {} orphaned skill(s) no longer in the source:",
+        orphans.len()
+    );
+    let mut removed = 0;
+    for (label, skill_name, path, ownership) in &orphans {
+        if *ownership == Ownership::Modified {
+            println!(
+                "  Keeping {} at {}: it was changed there since the last sync",
+                skill_name, label
+            );
+            continue;
+        }
+        if dry_run {
+            println!("[DRY RUN]   Would remove {} from {}", skill_name, label);
+            continue;
+        }
+        if matches!(*user_policy, OverwritePolicy::PerSkill) {
+            print!(
+                "  Skill '{}' is no longer in the source. Remove it from {}? [y/n/all] ",
+                skill_name, label
+            );
+            std::io::stdout().flush().context("Flush stdout")?;
+            let mut input = String::new();
+            std::io::stdin()
+                .read_line(&mut input)
+                .context("Failed to read user input")?;
+            match input.trim().to_lowercase().as_str() {
+                "y" | "yes" => {}
+                "a" | "all" => *user_policy = OverwritePolicy::All,
+                _ => {
+                    println!("    Kept {} at {}", skill_name, label);
+                    continue;
+                }
+            }
+        }
+        remove_existing_path(path)?;
+        if let Err(e) = registry::remove_path(skill_name, &path.to_string_lossy()) {
+            eprintln!(
+                "Warning: failed to update registry for '{}': {}",
+                skill_name, e
+            );
+        }
+        if let Some(target_dir) = path.parent() {
+            managed::forget(target_dir, skill_name)?;
+        }
+        println!("  Removed {} from {}", skill_name, label);
+        removed += 1;
+    }
+
+    if dry_run {
+        println!("[DRY RUN] Prune complete. No changes were made.");
+    } else {
+        println!("Pruned {} skill(s).", removed);
+    }
+    Ok(())
+}
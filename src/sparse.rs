@@ -0,0 +1,364 @@
+use crate::deps;
+use crate::install::{git_output, glob_match, relative_subdir, ResolvedPackage};
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+
+/// True if the clone rooted at `repo_dir` has a sparse checkout (packages are cloned with
+/// `--sparse`).
+pub fn is_sparse(repo_dir: &Path) -> bool {
+    repo_dir.join(".git").exists()
+        && git_output(repo_dir, &["config", "--bool", "core.sparseCheckout"]).as_deref()
+            == Some("true")
+}
+
+/// True if `path` (relative to the repo root) exists in HEAD, whether or not it is checked out.
+pub fn in_head(repo_dir: &Path, path: &str) -> bool {
+    let object = format!("HEAD:{}", path);
+    git_output(repo_dir, &["rev-parse", "--verify", "--quiet", &object]).is_some()
+}
+
+/// Files in HEAD under `base` (the whole tree when None). Only trees are read, so this works
+/// without the blobs of a partial clone.
+fn tree_files(repo_dir: &Path, base: Option<&str>) -> Vec<String> {
+    let mut args = vec!["ls-tree", "-r", "--name-only", "HEAD"];
+    if let Some(base) = base {
+        args.push(base);
+    }
+    git_output(repo_dir, &args)
+        .map(|out| out.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// In offline mode nothing outside the cached checkout can be fetched. Returns true (after
+/// saying why) when `paths` would need files that aren't checked out yet.
+fn skip_offline(repo_dir: &Path, paths: &[String], offline: bool) -> bool {
+    if !offline {
+        return false;
+    }
+    let missing: Vec<&str> = paths
+        .iter()
+        .filter(|path| path.is_empty() || !repo_dir.join(path).exists())
+        .map(|path| if path.is_empty() { "." } else { path.as_str() })
+        .collect();
+    if !missing.is_empty() {
+        eprintln!(
+            "Warning: offline mode is on, so {} can't be checked out of the cached sparse clone at {} (their files are fetched on demand).\nHint: Run once without --offline, or set install.partial_clone to false for full clones.",
+            missing.join(", "),
+            repo_dir.display()
+        );
+    }
+    true
+}
+
+fn sparse_checkout(repo_dir: &Path, args: &[String], what: &str) -> Result<()> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo_dir)
+        .arg("sparse-checkout")
+        .args(args)
+        .status()
+        .context("Failed to run git sparse-checkout. Is git installed?")?;
+    if !status.success() {
+        anyhow::bail!(
+            "Failed to check out {} in {}\nHint: Files outside the cached sparse checkout are fetched on demand, which needs the network.",
+            what,
+            repo_dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// Add directories (relative to the repo root) to a sparse checkout, fetching their files.
+/// The repo root itself can't be added; use `disable` for a skill that spans the whole tree.
+pub fn add(repo_dir: &Path, dirs: &[String], offline: bool) -> Result<()> {
+    if dirs.is_empty() || !is_sparse(repo_dir) {
+        return Ok(());
+    }
+    if dirs.iter().any(|dir| dir.is_empty()) {
+        anyhow::bail!(
+            "Can't add the repo root to a sparse checkout; check out the whole tree instead"
+        );
+    }
+    if skip_offline(repo_dir, dirs, offline) {
+        return Ok(());
+    }
+    let mut args = vec!["add".to_string()];
+    args.extend(dirs.iter().cloned());
+    sparse_checkout(repo_dir, &args, &dirs.join(", "))
+}
+
+/// Turn the sparse checkout off and check out the whole tree, for a skill at the repo root.
+pub fn disable(repo_dir: &Path, offline: bool) -> Result<()> {
+    if !is_sparse(repo_dir) || skip_offline(repo_dir, &[String::new()], offline) {
+        return Ok(());
+    }
+    sparse_checkout(repo_dir, &["disable".to_string()], "the whole tree")
+}
+
+/// Check out the installed skill directories (`None` for a skill at the repo root).
+pub fn add_skills(repo_dir: &Path, subdirs: &[Option<String>], offline: bool) -> Result<()> {
+    // A skill at the repo root is the whole checkout, so nothing narrower would do.
+    if subdirs
+        .iter()
+        .any(|subdir| subdir.as_deref().unwrap_or_default().is_empty())
+    {
+        return disable(repo_dir, offline);
+    }
+    let dirs: Vec<String> = subdirs.iter().flatten().cloned().collect();
+    add(repo_dir, &dirs, offline)
+}
+
+fn join(dir: &str, name: &str) -> String {
+    match (dir.is_empty(), name.is_empty()) {
+        (true, _) => name.to_string(),
+        (_, true) => dir.to_string(),
+        _ => format!("{}/{}", dir, name),
+    }
+}
+
+/// Check out what an install needs from a sparse clone: the skills matching `patterns` (every
+/// skill when empty, or when a pattern matches nothing so errors can list what exists) in the
+/// package directory or the first of its `skill_dirs`, plus the skills they require.
+/// Full clones, local paths, and archives are left alone, and `offline` only uses what is
+/// already checked out.
+pub fn include(
+    package: &ResolvedPackage,
+    skill_dirs: &[String],
+    patterns: &[String],
+    offline: bool,
+) -> Result<()> {
+    if !package.is_git() || !is_sparse(&package.root) {
+        return Ok(());
+    }
+    let base = relative_subdir(&package.root, &package.path).unwrap_or_default();
+    let files = tree_files(&package.root, (!base.is_empty()).then_some(base.as_str()));
+    let skill_paths: BTreeSet<&str> = files
+        .iter()
+        .filter_map(|file| file.strip_suffix("SKILL.md"))
+        .filter(|dir| dir.is_empty() || dir.ends_with('/'))
+        .map(|dir| dir.trim_end_matches('/'))
+        .collect();
+
+    // The package itself is a skill.
+    if skill_paths.contains(base.as_str()) {
+        return add_skills(&package.root, &[Some(base)], offline);
+    }
+
+    // Same search order as find_installable_skills: direct skills, then the first skill dir.
+    let in_dir = |dir: &str| -> Vec<(String, String)> {
+        skill_paths
+            .iter()
+            .filter_map(|path| {
+                let name = if dir.is_empty() {
+                    path
+                } else {
+                    path.strip_prefix(dir)?.strip_prefix('/')?
+                };
+                (!name.contains('/')).then(|| (name.to_string(), path.to_string()))
+            })
+            .collect()
+    };
+    let mut candidates = in_dir(&base);
+    if candidates.is_empty() {
+        let prefix_of = |dir: &str| format!("{}/", dir);
+        let Some(dir) = skill_dirs
+            .iter()
+            .map(|dir| join(&base, dir.trim_matches('/')))
+            .find(|dir| files.iter().any(|file| file.starts_with(&prefix_of(dir))))
+        else {
+            return Ok(());
+        };
+        candidates = in_dir(&dir);
+        if candidates.is_empty() {
+            return add(&package.root, &[dir], offline);
+        }
+    }
+
+    let all_match = patterns
+        .iter()
+        .all(|pattern| candidates.iter().any(|(name, _)| glob_match(pattern, name)));
+    let mut wanted: Vec<&(String, String)> = candidates
+        .iter()
+        .filter(|(name, _)| {
+            !all_match || patterns.is_empty() || patterns.iter().any(|p| glob_match(p, name))
+        })
+        .collect();
+
+    // Follow requires: until every required skill from this package is checked out.
+    let mut added = 0;
+    while added < wanted.len() {
+        let batch: Vec<String> = wanted[added..]
+            .iter()
+            .map(|(_, path)| path.clone())
+            .collect();
+        add(&package.root, &batch, offline)?;
+        added = wanted.len();
+        for path in batch {
+            for requirement in deps::skill_requirements(&package.root.join(&path)) {
+                if requirement.package.is_some() {
+                    continue;
+                }
+                if let Some(skill) = candidates
+                    .iter()
+                    .find(|(name, _)| *name == requirement.skill)
+                {
+                    if !wanted.contains(&skill) {
+                        wanted.push(skill);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_sparse_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn write_skill(dir: &Path, requires: &str) {
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: s\ndescription: d\nrequires: [{}]\n---\n",
+                requires
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("scripts").join("run.sh"), "echo hi").unwrap();
+    }
+
+    fn sparse_clone(upstream: &Path, clone: &Path) -> ResolvedPackage {
+        let url = format!("file://{}", upstream.display());
+        let status = Command::new("git")
+            .args([
+                "clone",
+                "-q",
+                "--depth",
+                "1",
+                "--filter=blob:none",
+                "--sparse",
+            ])
+            .arg(&url)
+            .arg(clone)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success());
+        ResolvedPackage {
+            path: clone.to_path_buf(),
+            root: clone.to_path_buf(),
+            url: Some(url),
+        }
+    }
+
+    #[test]
+    fn include_checks_out_requested_skills_and_their_requirements() {
+        let tmp = unique_tmp("include");
+        let _ = fs::remove_dir_all(&tmp);
+        let upstream = tmp.join("upstream");
+        write_skill(&upstream.join("skills").join("deploy"), "k8s");
+        write_skill(&upstream.join("skills").join("k8s"), "");
+        write_skill(&upstream.join("skills").join("pdf"), "");
+        write_skill(&upstream.join("skills").join("xlsx"), "");
+        fs::create_dir_all(upstream.join("src")).unwrap();
+        fs::write(upstream.join("src").join("main.rs"), "fn main() {}").unwrap();
+        fs::write(upstream.join("LICENSE"), "MIT").unwrap();
+        git(&upstream, &["init", "-q"]);
+        git(&upstream, &["config", "uploadpack.allowFilter", "true"]);
+        git(&upstream, &["add", "."]);
+        git(&upstream, &["commit", "-q", "-m", "skills"]);
+
+        let clone = tmp.join("clone");
+        let package = sparse_clone(&upstream, &clone);
+        let dirs = vec!["skills".to_string()];
+        let sparse = is_sparse(&clone);
+        let cloned_skills = clone.join("skills").exists();
+        include(&package, &dirs, &["deploy".to_string()], false).unwrap();
+        let first = (
+            clone.join("skills/deploy/scripts/run.sh").is_file(),
+            clone.join("skills/k8s/SKILL.md").is_file(),
+            clone.join("skills/pdf").exists(),
+            clone.join("LICENSE").is_file(),
+        );
+        include(&package, &dirs, &["pdf".to_string()], false).unwrap();
+        let second = (
+            clone.join("skills/deploy").is_dir(),
+            clone.join("skills/pdf").is_dir(),
+            clone.join("skills/xlsx").exists(),
+            clone.join("src").exists(),
+        );
+        include(&package, &dirs, &["missing".to_string()], false).unwrap();
+        let unmatched = clone.join("skills/xlsx").is_dir() && !clone.join("src").exists();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(sparse);
+        assert!(!cloned_skills);
+        assert_eq!(first, (true, true, false, true));
+        assert_eq!(second, (true, true, false, false));
+        assert!(unmatched);
+    }
+
+    #[test]
+    fn root_skills_check_out_the_whole_tree_and_offline_fetches_nothing() {
+        let tmp = unique_tmp("root");
+        let _ = fs::remove_dir_all(&tmp);
+        let upstream = tmp.join("upstream");
+        write_skill(&upstream, "");
+        write_skill(&upstream.join("skills").join("pdf"), "");
+        git(&upstream, &["init", "-q"]);
+        git(&upstream, &["config", "uploadpack.allowFilter", "true"]);
+        git(&upstream, &["add", "."]);
+        git(&upstream, &["commit", "-q", "-m", "skills"]);
+
+        let offline_clone = tmp.join("offline");
+        sparse_clone(&upstream, &offline_clone);
+        add_skills(&offline_clone, &[Some("skills/pdf".to_string())], true).unwrap();
+        add_skills(&offline_clone, &[None], true).unwrap();
+        let offline = (
+            is_sparse(&offline_clone),
+            offline_clone.join("skills").exists(),
+            offline_clone.join("scripts").exists(),
+        );
+
+        let clone = tmp.join("clone");
+        sparse_clone(&upstream, &clone);
+        let root_rejected = add(&clone, &[String::new()], false).is_err();
+        add_skills(&clone, &[Some("skills/pdf".to_string()), None], false).unwrap();
+        let whole = (
+            is_sparse(&clone),
+            clone.join("scripts").join("run.sh").is_file(),
+            clone.join("skills/pdf/SKILL.md").is_file(),
+        );
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(offline, (true, false, false));
+        assert!(root_rejected);
+        assert_eq!(whole, (false, true, true));
+    }
+}
@@ -0,0 +1,349 @@
+use crate::config::InstallConfig;
+use crate::hash::{file_hashes, tree_hash};
+use crate::install::{confirm, git_output, resolve_package, Fetch, RenameStage};
+use crate::license;
+use crate::lockfile::{self, LockedSkill};
+use crate::scan;
+use crate::signature;
+use crate::skills::{copy_skill, show_diff};
+use crate::sparse;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Lockfile entries are grouped by (package spec, ref).
+type PackageKey = (String, Option<String>);
+
+/// How an installed skill compares with its package upstream.
+#[derive(Debug, PartialEq)]
+pub enum UpstreamStatus {
+    UpToDate,
+    Changed,
+    RemovedUpstream,
+}
+
+/// An installed skill checked against the refreshed package cache.
+pub struct SkillCheck {
+    pub name: String,
+    pub locked: LockedSkill,
+    pub status: UpstreamStatus,
+    /// Commit the package cache is at after refreshing (None for non-Git packages)
+    pub upstream_commit: Option<String>,
+    /// Skill directory in the refreshed package
+    pub upstream_path: PathBuf,
+    /// True when the installed copy no longer matches the hash recorded at install time
+    pub modified_locally: bool,
+    /// License of the upstream skill
+    pub license: Option<String>,
+}
+
+/// Compare a locked skill with its current upstream directory.
+pub fn upstream_status(locked: &LockedSkill, upstream: &Path) -> Result<UpstreamStatus> {
+    if !upstream.join("SKILL.md").is_file() {
+        return Ok(UpstreamStatus::RemovedUpstream);
+    }
+    if tree_hash(upstream)? == locked.hash {
+        Ok(UpstreamStatus::UpToDate)
+    } else {
+        Ok(UpstreamStatus::Changed)
+    }
+}
+
+/// Refresh every package in the lockfile and compare its skills with upstream.
+/// `only` limits the check to a skill name or package spec. Renamed skills are compared
+/// against a copy staged in `stage` under their installed name. Offline, packages are
+/// compared as they are in the cache instead of being refreshed. With `check_signatures`,
+/// each refreshed package must pass the signature policy in `config` before it is compared.
+pub fn check_installed(
+    source_dir: &Path,
+    only: Option<&str>,
+    config: &InstallConfig,
+    offline: bool,
+    check_signatures: bool,
+    stage: &RenameStage,
+) -> Result<Vec<SkillCheck>> {
+    let lock = lockfile::load(source_dir)?;
+
+    // Group skills by package so each package is fetched once.
+    let mut packages: BTreeMap<PackageKey, Vec<(String, LockedSkill)>> = BTreeMap::new();
+    for (name, locked) in lock.skills {
+        if let Some(filter) = only {
+            if name != filter && locked.package != filter {
+                continue;
+            }
+        }
+        packages
+            .entry((locked.package.clone(), locked.git_ref.clone()))
+            .or_default()
+            .push((name, locked));
+    }
+
+    let mut checks = Vec::new();
+    for ((spec, git_ref), skills) in packages {
+        let is_remote = skills.iter().any(|(_, locked)| locked.url.is_some());
+        let fetch = match (offline, is_remote) {
+            (true, _) => Fetch::Offline,
+            (false, true) => Fetch::Refresh,
+            (false, false) => Fetch::IfMissing,
+        };
+        let package = resolve_package(&spec, git_ref.as_deref(), config, fetch)?;
+        let installed_dirs: Vec<Option<String>> = skills
+            .iter()
+            .map(|(_, locked)| locked.subdir.clone())
+            .collect();
+        sparse::add_skills(&package.root, &installed_dirs, offline)?;
+        if check_signatures {
+            signature::enforce(&spec, &package, git_ref.as_deref(), &config.signing)?;
+        }
+        let upstream_commit = if package.is_git() {
+            git_output(&package.root, &["rev-parse", "HEAD"])
+        } else {
+            None
+        };
+
+        for (name, locked) in skills {
+            let mut upstream_path = match &locked.subdir {
+                Some(subdir) => package.root.join(subdir),
+                None => package.root.clone(),
+            };
+            let license = license::skill_license(&upstream_path, &package.root);
+            if locked.skill.is_some() && upstream_path.join("SKILL.md").is_file() {
+                upstream_path = stage.stage(&upstream_path, &name)?;
+            }
+            let installed = source_dir.join(&name);
+            let modified_locally = installed.is_dir() && tree_hash(&installed)? != locked.hash;
+            checks.push(SkillCheck {
+                status: upstream_status(&locked, &upstream_path)?,
+                name,
+                locked,
+                upstream_commit: upstream_commit.clone(),
+                upstream_path,
+                modified_locally,
+                license,
+            });
+        }
+    }
+
+    Ok(checks)
+}
+
+fn short_commit(commit: Option<&str>) -> &str {
+    commit.map(|c| &c[..c.len().min(7)]).unwrap_or("-")
+}
+
+fn print_check(check: &SkillCheck) {
+    let status = match check.status {
+        UpstreamStatus::UpToDate => "up to date",
+        UpstreamStatus::Changed => "changed upstream",
+        UpstreamStatus::RemovedUpstream => "removed upstream",
+    };
+    let local = if check.modified_locally {
+        " (modified locally)"
+    } else {
+        ""
+    };
+    println!(
+        "  {}  {}  {} → {}  {}{}",
+        check.name,
+        check.locked.package,
+        short_commit(check.locked.commit.as_deref()),
+        short_commit(check.upstream_commit.as_deref()),
+        status,
+        local
+    );
+}
+
+/// Report installed skills whose upstream package has changed.
+pub fn outdated(source_dir: &Path, config: &InstallConfig, offline: bool) -> Result<()> {
+    let stage = RenameStage::new();
+    let checks = check_installed(source_dir, None, config, offline, false, &stage)?;
+    if checks.is_empty() {
+        println!(
+            "No installed packages recorded in {}",
+            lockfile::lock_path(source_dir).display()
+        );
+        return Ok(());
+    }
+
+    let outdated: Vec<&SkillCheck> = checks
+        .iter()
+        .filter(|c| c.status != UpstreamStatus::UpToDate)
+        .collect();
+
+    println!();
+    if outdated.is_empty() {
+        println!("All {} installed skill(s) are up to date.", checks.len());
+        return Ok(());
+    }
+
+    println!("Outdated skills ({} of {}):", outdated.len(), checks.len());
+    for check in outdated {
+        print_check(check);
+    }
+    println!("\nRun `skillset update` to install the upstream changes.");
+    Ok(())
+}
+
+/// Re-install skills that changed upstream, showing a diff of each before overwriting.
+/// `only` limits the update to a skill name or package spec. Upstream packages must pass
+/// the signature policy in `config` before anything is copied.
+pub fn update(
+    source_dir: &Path,
+    only: Option<&str>,
+    force: bool,
+    dry_run: bool,
+    config: &InstallConfig,
+    offline: bool,
+) -> Result<()> {
+    let stage = RenameStage::new();
+    let checks = check_installed(source_dir, only, config, offline, true, &stage)?;
+    if checks.is_empty() {
+        match only {
+            Some(name) => anyhow::bail!(
+                "No installed skill or package named '{}' in {}",
+                name,
+                lockfile::lock_path(source_dir).display()
+            ),
+            None => println!(
+                "No installed packages recorded in {}",
+                lockfile::lock_path(source_dir).display()
+            ),
+        }
+        return Ok(());
+    }
+
+    for check in checks
+        .iter()
+        .filter(|c| c.status == UpstreamStatus::RemovedUpstream)
+    {
+        println!(
+            "Warning: '{}' no longer exists in {}; leaving the installed copy in place.",
+            check.name, check.locked.package
+        );
+    }
+
+    let changed: Vec<&SkillCheck> = checks
+        .iter()
+        .filter(|c| c.status == UpstreamStatus::Changed)
+        .collect();
+    if changed.is_empty() {
+        println!("All {} installed skill(s) are up to date.", checks.len());
+        return Ok(());
+    }
+
+    let licenses: Vec<(&str, Option<&str>)> = changed
+        .iter()
+        .map(|c| (c.name.as_str(), c.license.as_deref()))
+        .collect();
+    license::enforce(&licenses, config)?;
+    if !dry_run {
+        let scanned: Vec<(&str, &Path)> = changed
+            .iter()
+            .map(|c| (c.name.as_str(), c.upstream_path.as_path()))
+            .collect();
+        scan::review(&scanned, &config.scan, force)?;
+    }
+
+    let mut updated = Vec::new();
+    for check in changed {
+        println!();
+        print_check(check);
+        let installed = source_dir.join(&check.name);
+        if installed.is_dir() {
+            show_diff(&check.upstream_path, &installed, &check.name, "installed");
+        }
+
+        if dry_run {
+            println!("[DRY RUN]   Would update {}", check.name);
+            continue;
+        }
+
+        let prompt = if check.modified_locally {
+            format!(
+                "  '{}' has local modifications that will be lost. Update anyway?",
+                check.name
+            )
+        } else {
+            format!("  Update '{}'?", check.name)
+        };
+        if !force && !confirm(&prompt)? {
+            println!("    Skipped {}", check.name);
+            continue;
+        }
+
+        copy_skill(&check.upstream_path, &installed)?;
+        let entry = LockedSkill {
+            commit: check.upstream_commit.clone(),
+            hash: tree_hash(&installed)?,
+            files: file_hashes(&installed)?,
+            license: check.license.clone(),
+            ..check.locked.clone()
+        };
+        println!("  Updated {}", check.name);
+        updated.push((check.name.clone(), entry));
+    }
+
+    if dry_run {
+        println!("\n[DRY RUN] No changes made.");
+        return Ok(());
+    }
+
+    let count = updated.len();
+    lockfile::record(source_dir, updated)?;
+    println!("\nUpdated {} skill(s).", count);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_update_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn locked(hash: String) -> LockedSkill {
+        LockedSkill {
+            package: "org/repo".to_string(),
+            git_ref: None,
+            url: None,
+            commit: None,
+            subdir: Some("skills/my-skill".to_string()),
+            skill: None,
+            license: None,
+            hash,
+            files: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn upstream_status_detects_changes_and_removals() {
+        let tmp = unique_tmp("status");
+        let _ = fs::remove_dir_all(&tmp);
+        let skill = tmp.join("my-skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# v1").unwrap();
+        let entry = locked(tree_hash(&skill).unwrap());
+
+        let unchanged = upstream_status(&entry, &skill).unwrap();
+        fs::write(skill.join("helper.md"), "new file").unwrap();
+        let changed = upstream_status(&entry, &skill).unwrap();
+        let removed = upstream_status(&entry, &tmp.join("missing")).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert_eq!(unchanged, UpstreamStatus::UpToDate);
+        assert_eq!(changed, UpstreamStatus::Changed);
+        assert_eq!(removed, UpstreamStatus::RemovedUpstream);
+    }
+
+    #[test]
+    fn short_commit_truncates_and_handles_missing() {
+        assert_eq!(short_commit(Some("0123456789abcdef")), "0123456");
+        assert_eq!(short_commit(None), "-");
+    }
+}
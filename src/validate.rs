@@ -1,3 +1,4 @@
+use crate::deps;
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
@@ -10,6 +11,20 @@ pub struct SkillMeta {
     pub author: Option<String>,
     pub version: Option<String>,
     pub tags: Vec<String>,
+    /// SPDX license identifier or expression
+    pub license: Option<String>,
+    /// Skills this one depends on: `skill` or `package-spec::skill`
+    pub requires: Vec<String>,
+}
+
+/// Parse an inline `[a, b]` list (or a single bare value) into its items.
+fn parse_inline_list(value: &str) -> Vec<String> {
+    let stripped = value.trim_start_matches('[').trim_end_matches(']');
+    stripped
+        .split(',')
+        .map(|s| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
 }
 
 /// Parse YAML-like frontmatter from a SKILL.md file.
@@ -25,6 +40,8 @@ pub fn parse_frontmatter(content: &str) -> Option<SkillMeta> {
     let yaml_block = &after_first[..end];
 
     let mut meta = SkillMeta::default();
+    // `requires:` may also be written as a block list of `- item` lines.
+    let mut in_requires = false;
 
     for line in yaml_block.lines() {
         let line = line.trim();
@@ -32,6 +49,14 @@ pub fn parse_frontmatter(content: &str) -> Option<SkillMeta> {
             continue;
         }
 
+        if in_requires {
+            if let Some(item) = line.strip_prefix('-') {
+                meta.requires.extend(parse_inline_list(item.trim()));
+                continue;
+            }
+            in_requires = false;
+        }
+
         if let Some((key, value)) = line.split_once(':') {
             let key = key.trim();
             let value = value.trim();
@@ -41,6 +66,7 @@ pub fn parse_frontmatter(content: &str) -> Option<SkillMeta> {
                 "description" => meta.description = Some(value.to_string()),
                 "author" => meta.author = Some(value.to_string()),
                 "version" => meta.version = Some(value.to_string()),
+                "license" => meta.license = Some(value.to_string()),
                 "tags" => {
                     // Parse [tag1, tag2, tag3] format
                     let stripped = value.trim_start_matches('[').trim_end_matches(']');
@@ -50,6 +76,10 @@ pub fn parse_frontmatter(content: &str) -> Option<SkillMeta> {
                         .filter(|s| !s.is_empty())
                         .collect();
                 }
+                "requires" => {
+                    meta.requires = parse_inline_list(value);
+                    in_requires = value.is_empty();
+                }
                 _ => {}
             }
         }
@@ -58,10 +88,54 @@ pub fn parse_frontmatter(content: &str) -> Option<SkillMeta> {
     Some(meta)
 }
 
+/// Set the `name:` field in SKILL.md frontmatter, adding the field (or a frontmatter block)
+/// when it is missing. Other lines are left untouched.
+pub fn set_frontmatter_name(content: &str, name: &str) -> String {
+    let name_line = format!("name: {}", name);
+    let leading = &content[..content.len() - content.trim_start().len()];
+    let body = content.trim_start();
+
+    let Some(after_first) = body.strip_prefix("---") else {
+        return format!("---\n{}\n---\n{}", name_line, content);
+    };
+    let Some(end) = after_first.find("---") else {
+        return format!("---\n{}\n---\n{}", name_line, content);
+    };
+
+    let block = &after_first[..end];
+    let mut replaced = false;
+    let mut lines: Vec<String> = block
+        .split('\n')
+        .map(|line| {
+            let is_name = line.split_once(':').is_some_and(|(key, _)| {
+                key.trim() == "name" && !line.starts_with(char::is_whitespace)
+            });
+            if is_name && !replaced {
+                replaced = true;
+                name_line.clone()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        // The block starts right after `---`, so its first line is empty (the line break).
+        lines.insert(1.min(lines.len()), name_line);
+    }
+
+    format!(
+        "{}---{}---{}",
+        leading,
+        lines.join("\n"),
+        &after_first[end + 3..]
+    )
+}
+
 /// Validate all skills in the source directory.
 pub fn validate_skills(source: &Path, skills: &[String]) -> Result<()> {
     let mut errors = 0;
     let mut warnings = 0;
+    let missing = deps::missing_requirements(source).unwrap_or_default();
 
     println!("Validating {} skill(s):\n", skills.len());
 
@@ -91,13 +165,13 @@ pub fn validate_skills(source: &Path, skills: &[String]) -> Result<()> {
                 errors += 1;
             }
             Some(meta) => {
-                let mut issues = Vec::new();
+                let mut issues: Vec<String> = Vec::new();
 
                 if meta.name.is_none() || meta.name.as_deref() == Some("") {
-                    issues.push("missing 'name'");
+                    issues.push("missing 'name'".to_string());
                 }
                 if meta.description.is_none() || meta.description.as_deref() == Some("") {
-                    issues.push("missing 'description'");
+                    issues.push("missing 'description'".to_string());
                 }
                 if meta
                     .description
@@ -105,7 +179,13 @@ pub fn validate_skills(source: &Path, skills: &[String]) -> Result<()> {
                     .map(|d| d.starts_with("A brief description"))
                     .unwrap_or(false)
                 {
-                    issues.push("description is still the template placeholder");
+                    issues.push("description is still the template placeholder".to_string());
+                }
+                for (_, requirement) in missing.iter().filter(|(skill, _)| skill == skill_name) {
+                    issues.push(format!(
+                        "requires '{}', which is not installed",
+                        requirement
+                    ));
                 }
 
                 if issues.is_empty() {
@@ -119,6 +199,12 @@ pub fn validate_skills(source: &Path, skills: &[String]) -> Result<()> {
                     if !meta.tags.is_empty() {
                         extras.push("tags");
                     }
+                    if meta.license.is_some() {
+                        extras.push("license");
+                    }
+                    if !meta.requires.is_empty() {
+                        extras.push("requires");
+                    }
                     let extra_str = if extras.is_empty() {
                         String::new()
                     } else {
@@ -165,6 +251,7 @@ name: my-skill
 description: Does something cool
 author: Glen
 version: 1.0.0
+license: MIT
 tags: [testing, automation]
 ---
 
@@ -175,6 +262,7 @@ tags: [testing, automation]
         assert_eq!(meta.description.as_deref(), Some("Does something cool"));
         assert_eq!(meta.author.as_deref(), Some("Glen"));
         assert_eq!(meta.version.as_deref(), Some("1.0.0"));
+        assert_eq!(meta.license.as_deref(), Some("MIT"));
         assert_eq!(meta.tags, vec!["testing", "automation"]);
     }
 
@@ -198,4 +286,41 @@ tags: [testing, automation]
         let meta = parse_frontmatter(content).unwrap();
         assert!(meta.tags.is_empty());
     }
+
+    #[test]
+    fn test_parse_frontmatter_requires_inline_and_block_lists() {
+        let inline = "---\nname: deploy\nrequires: [k8s-conventions, acme/ops::helm]\n---\n";
+        let meta = parse_frontmatter(inline).unwrap();
+        assert_eq!(meta.requires, vec!["k8s-conventions", "acme/ops::helm"]);
+
+        let block = "---\nname: deploy\nrequires:\n  - k8s-conventions\n  - \"acme/ops::helm\"\ndescription: d\n---\n";
+        let meta = parse_frontmatter(block).unwrap();
+        assert_eq!(meta.requires, vec!["k8s-conventions", "acme/ops::helm"]);
+        assert_eq!(meta.description.as_deref(), Some("d"));
+    }
+
+    #[test]
+    fn test_set_frontmatter_name_replaces_existing_name() {
+        let content = "---\nname: code-review\ndescription: Review code\n---\n# Body\nname: keep\n";
+        let renamed = set_frontmatter_name(content, "acme-code-review");
+        assert_eq!(
+            renamed,
+            "---\nname: acme-code-review\ndescription: Review code\n---\n# Body\nname: keep\n"
+        );
+        let meta = parse_frontmatter(&renamed).unwrap();
+        assert_eq!(meta.name.as_deref(), Some("acme-code-review"));
+        assert_eq!(meta.description.as_deref(), Some("Review code"));
+    }
+
+    #[test]
+    fn test_set_frontmatter_name_adds_missing_name() {
+        let renamed = set_frontmatter_name("---\ndescription: d\n---\n# Body", "new");
+        assert_eq!(renamed, "---\nname: new\ndescription: d\n---\n# Body");
+
+        let renamed = set_frontmatter_name("# No frontmatter", "new");
+        assert_eq!(
+            parse_frontmatter(&renamed).unwrap().name.as_deref(),
+            Some("new")
+        );
+    }
 }
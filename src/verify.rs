@@ -0,0 +1,217 @@
+use crate::hash::{file_hashes, tree_hash};
+use crate::lockfile::{self, LockedSkill};
+use crate::skills::discover_skills;
+use anyhow::Result;
+use std::path::Path;
+
+/// Result of checking one installed skill against its lockfile entry.
+#[derive(Debug, Default, PartialEq)]
+pub struct SkillReport {
+    pub name: String,
+    /// The skill directory no longer exists
+    pub missing: bool,
+    pub modified: Vec<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Tree hash differs but the lockfile has no per-file hashes to pinpoint the change
+    pub changed_without_files: bool,
+}
+
+impl SkillReport {
+    pub fn is_ok(&self) -> bool {
+        !self.missing
+            && !self.changed_without_files
+            && self.modified.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
+/// Recompute an installed skill's hashes and compare them with its lockfile entry.
+pub fn verify_skill(skill_dir: &Path, name: &str, locked: &LockedSkill) -> Result<SkillReport> {
+    let mut report = SkillReport {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    if !skill_dir.is_dir() {
+        report.missing = true;
+        return Ok(report);
+    }
+    if tree_hash(skill_dir)? == locked.hash {
+        return Ok(report);
+    }
+    if locked.files.is_empty() {
+        report.changed_without_files = true;
+        return Ok(report);
+    }
+
+    let current = file_hashes(skill_dir)?;
+    for (path, hash) in &current {
+        match locked.files.get(path) {
+            None => report.added.push(path.clone()),
+            Some(expected) if expected != hash => report.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    report.removed = locked
+        .files
+        .keys()
+        .filter(|path| !current.contains_key(*path))
+        .cloned()
+        .collect();
+    Ok(report)
+}
+
+fn print_report(report: &SkillReport) {
+    if report.is_ok() {
+        println!("  ✓ {}", report.name);
+        return;
+    }
+    if report.missing {
+        println!("  ✗ {} — missing from source", report.name);
+        return;
+    }
+    if report.changed_without_files {
+        println!(
+            "  ✗ {} — contents changed (lockfile has no per-file hashes; re-install to record them)",
+            report.name
+        );
+        return;
+    }
+    println!("  ✗ {}", report.name);
+    for path in &report.modified {
+        println!("      modified: {}", path);
+    }
+    for path in &report.added {
+        println!("      added:    {}", path);
+    }
+    for path in &report.removed {
+        println!("      removed:  {}", path);
+    }
+}
+
+/// Verify every skill recorded in the lockfile. Fails if any skill was modified,
+/// gained or lost files, or is missing.
+pub fn verify(source_dir: &Path) -> Result<()> {
+    let lock = lockfile::load(source_dir)?;
+    if lock.skills.is_empty() {
+        println!(
+            "No installed skills recorded in {}",
+            lockfile::lock_path(source_dir).display()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Verifying {} skill(s) against {}:\n",
+        lock.skills.len(),
+        lockfile::lock_path(source_dir).display()
+    );
+    let mut failed = 0;
+    for (name, locked) in &lock.skills {
+        let report = verify_skill(&source_dir.join(name), name, locked)?;
+        if !report.is_ok() {
+            failed += 1;
+        }
+        print_report(&report);
+    }
+
+    let untracked: Vec<String> = discover_skills(source_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| !lock.skills.contains_key(name))
+        .collect();
+    if !untracked.is_empty() {
+        println!(
+            "\nNot installed from a package (not verified): {}",
+            untracked.join(", ")
+        );
+    }
+
+    println!();
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} skill(s) failed verification\nHint: Run `skillset install --force` to restore the packages listed in the manifest.",
+            failed,
+            lock.skills.len()
+        );
+    }
+    println!("All {} skill(s) match the lockfile.", lock.skills.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn unique_tmp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "skillset_verify_test_{}_{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn locked_for(dir: &Path) -> LockedSkill {
+        LockedSkill {
+            package: "org/repo".to_string(),
+            git_ref: None,
+            url: None,
+            commit: None,
+            subdir: None,
+            skill: None,
+            license: None,
+            hash: tree_hash(dir).unwrap(),
+            files: file_hashes(dir).unwrap(),
+        }
+    }
+
+    #[test]
+    fn verify_skill_reports_modified_added_and_removed_files() {
+        let tmp = unique_tmp("files");
+        let _ = fs::remove_dir_all(&tmp);
+        let skill = tmp.join("my-skill");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(skill.join("SKILL.md"), "# My Skill").unwrap();
+        fs::write(skill.join("scripts").join("run.sh"), "echo hi").unwrap();
+        let locked = locked_for(&skill);
+        let clean = verify_skill(&skill, "my-skill", &locked).unwrap();
+
+        fs::write(skill.join("SKILL.md"), "# Tampered").unwrap();
+        fs::remove_file(skill.join("scripts").join("run.sh")).unwrap();
+        fs::write(skill.join("extra.md"), "new").unwrap();
+        let tampered = verify_skill(&skill, "my-skill", &locked).unwrap();
+        let missing = verify_skill(&tmp.join("gone"), "gone", &locked).unwrap();
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(clean.is_ok());
+        assert_eq!(tampered.modified, vec!["SKILL.md"]);
+        assert_eq!(tampered.added, vec!["extra.md"]);
+        assert_eq!(tampered.removed, vec!["scripts/run.sh"]);
+        assert!(missing.missing && !missing.is_ok());
+    }
+
+    #[test]
+    fn verify_fails_when_a_skill_changed() {
+        let tmp = unique_tmp("command");
+        let _ = fs::remove_dir_all(&tmp);
+        let source = tmp.join(".skillset").join("skills");
+        let skill = source.join("my-skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# My Skill").unwrap();
+        lockfile::record(&source, vec![("my-skill".to_string(), locked_for(&skill))]).unwrap();
+
+        let before = verify(&source);
+        fs::write(skill.join("SKILL.md"), "# Edited").unwrap();
+        let after = verify(&source);
+
+        fs::remove_dir_all(&tmp).ok();
+        assert!(before.is_ok());
+        assert!(after
+            .unwrap_err()
+            .to_string()
+            .contains("failed verification"));
+    }
+}